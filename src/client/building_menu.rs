@@ -0,0 +1,117 @@
+use crate::ClientGameState;
+use common::{
+    draw::{draw_rect_transform, draw_text_with_origin, TextOriginX, TextOriginY},
+    entity::EntityTag,
+    network::ClientMessage,
+    rect_transform::{point_inside, RectTransform},
+    world::find_entity,
+};
+use macroquad::{
+    color::{Color, BLACK, GRAY, WHITE},
+    input::is_mouse_button_released,
+    miniquad::MouseButton,
+};
+
+struct BuildingMenuOption {
+    transform: RectTransform,
+    text: String,
+    affordable: bool,
+    message: ClientMessage,
+}
+
+/// Sell and upgrade options shown next to the selected building, in world space
+fn building_menu_options(state: &ClientGameState) -> Vec<BuildingMenuOption> {
+    let server_controlled_game_state = &state.server_controlled_game_state;
+    let Some(entity_instance) = find_entity(
+        &server_controlled_game_state.dynamic_game_state.entities,
+        state.selected_entity_id,
+    ) else {
+        return Vec::new();
+    };
+    if state.spectating
+        || entity_instance.owner != state.player_id
+        || entity_instance.entity.tag != EntityTag::Tower
+    {
+        return Vec::new();
+    }
+    let energy = state.get_player().hand.energy;
+    let ruleset = &server_controlled_game_state.static_game_state.ruleset;
+    let refund =
+        (entity_instance.entity.energy_invested as f32 * ruleset.sell_refund_fraction) as i32;
+
+    let mut options = vec![(
+        format!("Sell (+{} energy)", refund),
+        true,
+        ClientMessage::SellBuilding(entity_instance.id),
+    )];
+    if let Some((upgrade_blueprint, upgrade_cost)) = entity_instance
+        .entity
+        .blueprint
+        .as_ref()
+        .and_then(|blueprint| blueprint.get_upgrade())
+        .filter(|_| entity_instance.entity.construction.is_none())
+    {
+        options.push((
+            format!(
+                "Upgrade to {:?} ({} energy)",
+                upgrade_blueprint, upgrade_cost
+            ),
+            energy >= upgrade_cost,
+            ClientMessage::UpgradeBuilding(entity_instance.id),
+        ));
+    }
+
+    let (w, h, margin) = (260.0, 32.0, 4.0);
+    options
+        .into_iter()
+        .enumerate()
+        .map(|(i, (text, affordable, message))| BuildingMenuOption {
+            transform: RectTransform {
+                x: entity_instance.pos.x + entity_instance.entity.radius + margin,
+                y: entity_instance.pos.y - entity_instance.entity.radius + i as f32 * (h + margin),
+                w,
+                h,
+                ..Default::default()
+            },
+            text,
+            affordable,
+            message,
+        })
+        .collect()
+}
+
+/// Returns true if the click was consumed by the menu
+pub fn building_menu_input(state: &mut ClientGameState) -> bool {
+    if !is_mouse_button_released(MouseButton::Left) {
+        return false;
+    }
+    let mouse_world_pos = state.view_state.get_mouse_world_pos();
+    let Some(option) = building_menu_options(state)
+        .into_iter()
+        .find(|option| point_inside(mouse_world_pos, &option.transform))
+    else {
+        return false;
+    };
+    if option.affordable {
+        state.client_network_state.push_command(option.message);
+        state.selected_entity_id = None;
+    }
+    true
+}
+
+pub fn draw_building_menu(state: &ClientGameState) {
+    for option in building_menu_options(state) {
+        draw_rect_transform(&option.transform, Color { a: 0.8, ..WHITE });
+        draw_text_with_origin(
+            &option.text,
+            option.transform.x + 8.0,
+            option.transform.y + option.transform.h / 2.0,
+            24.0,
+            0.0,
+            if option.affordable { BLACK } else { GRAY },
+            TextOriginX::Left,
+            TextOriginY::Center,
+            Some(&state.font),
+        );
+    }
+}
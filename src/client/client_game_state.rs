@@ -2,12 +2,15 @@ use crate::{deck_builder::DeckBuilder, network::ClientNetworkState, physical_han
 use common::{
     debug_draw_config::DebugDrawConfig,
     game_state::ServerControlledGameState,
+    get_unit_spawnpoints::get_unit_spawnpoints,
     hit_numbers::HitNumbers,
     ids::{EntityId, PlayerId},
+    path_graph::{get_lane_choices, BranchPolicy},
     server_player::ServerPlayer,
     sprites::Sprites,
     view_state::ViewState,
 };
+use itertools::Itertools;
 use macroquad::text::Font;
 use std::time::SystemTime;
 
@@ -16,7 +19,10 @@ pub struct ClientGameState {
     pub server_controlled_game_state: ServerControlledGameState,
     pub client_network_state: ClientNetworkState,
     pub in_deck_builder: bool,
+    pub spectating: bool,
     pub selected_entity_id: Option<EntityId>,
+    /// Route given to units played on a spawnpoint
+    pub branch_policy: BranchPolicy,
     pub player_id: PlayerId,
     pub dt: f32,
     pub sprites: Sprites,
@@ -41,10 +47,12 @@ impl ClientGameState {
             client_network_state,
             time: SystemTime::now(),
             in_deck_builder: true,
+            spectating: false,
             debug_draw_config: DebugDrawConfig::default(),
             card_delta_angle: 0.1,
             relative_splay_radius: 4.5,
             selected_entity_id: None,
+            branch_policy: BranchPolicy::default(),
             dt: 0.167,
             sprites,
             font: macroquad::text::load_ttf_font("assets\\fonts\\shaky-hand-some-comic.bold.ttf")
@@ -60,8 +68,7 @@ impl ClientGameState {
         self.server_controlled_game_state
             .dynamic_game_state
             .players
-            .get(&self.player_id)
-            .is_some()
+            .contains_key(&self.player_id)
     }
     pub fn get_player(&self) -> &ServerPlayer {
         self.server_controlled_game_state
@@ -70,6 +77,52 @@ impl ClientGameState {
             .get(&self.player_id)
             .unwrap()
     }
+    pub fn cycle_perspective(&mut self) {
+        let mut player_ids = self
+            .server_controlled_game_state
+            .dynamic_game_state
+            .players
+            .keys()
+            .copied()
+            .collect_vec();
+        player_ids.sort_by_key(|player_id| player_id.0);
+        let next_idx = player_ids
+            .iter()
+            .position(|player_id| *player_id == self.player_id)
+            .map_or(0, |idx| idx + 1);
+        if let Some(player_id) = player_ids.get(next_idx).or(player_ids.first()) {
+            self.player_id = *player_id;
+        }
+    }
+    pub fn cycle_branch_policy(&mut self) {
+        let static_game_state = &self.server_controlled_game_state.static_game_state;
+        let lanes = get_unit_spawnpoints(
+            self.player_id,
+            static_game_state,
+            &self.server_controlled_game_state.dynamic_game_state,
+        )
+        .into_iter()
+        .flat_map(|spawnpoint| {
+            get_lane_choices(static_game_state, spawnpoint.path_id, &spawnpoint.direction)
+        })
+        .unique()
+        .map(BranchPolicy::FixedLane);
+        let branch_policies = [
+            BranchPolicy::ShortestToEnemyBase,
+            BranchPolicy::LeastDefended,
+        ]
+        .into_iter()
+        .chain(lanes)
+        .collect_vec();
+        let next_idx = branch_policies
+            .iter()
+            .position(|branch_policy| *branch_policy == self.branch_policy)
+            .map_or(0, |idx| idx + 1);
+        self.branch_policy = branch_policies
+            .get(next_idx)
+            .unwrap_or(&BranchPolicy::default())
+            .clone();
+    }
     pub fn step(&mut self) {
         let old_time = self.time;
         self.time = SystemTime::now();
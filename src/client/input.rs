@@ -1,5 +1,5 @@
-use crate::ClientGameState;
-use common::config::SCROLL_SENSITIVITY;
+use crate::{building_menu::building_menu_input, ClientGameState};
+use common::{config::SCROLL_SENSITIVITY, entity::EntityTag};
 use macroquad::{
     input::{
         is_key_down, is_key_pressed, is_mouse_button_released, mouse_position, mouse_wheel, KeyCode,
@@ -8,9 +8,6 @@ use macroquad::{
     miniquad::MouseButton,
 };
 
-#[derive(Debug, Default)]
-pub struct GameInput {}
-
 pub fn mouse_screen_pos_vec() -> Vec2 {
     let (x, y) = mouse_position();
     Vec2 { x, y }
@@ -20,12 +17,13 @@ pub fn main_input(state: &mut ClientGameState) {
     let (_, wheel_y) = mouse_wheel();
     state.view_state.normalized_scroll_y += wheel_y * SCROLL_SENSITIVITY;
     state.view_state.normalized_scroll_y = state.view_state.normalized_scroll_y.clamp(0.0, 1.0);
-    if is_mouse_button_released(MouseButton::Left) {
+    if !building_menu_input(state) && is_mouse_button_released(MouseButton::Left) {
         state.selected_entity_id = state
             .server_controlled_game_state
             .dynamic_game_state
             .entities
             .iter()
+            .filter(|entity_instance| entity_instance.entity.tag != EntityTag::Zone)
             .find_map(|entity_instance| {
                 ((entity_instance.pos - state.view_state.get_mouse_world_pos()).length()
                     < entity_instance.entity.radius)
@@ -40,6 +38,14 @@ pub fn main_input(state: &mut ClientGameState) {
         }
     }
 
+    if state.spectating && (is_key_pressed(KeyCode::Tab) || !state.has_player()) {
+        state.cycle_perspective();
+    }
+
+    if is_key_pressed(KeyCode::R) {
+        state.cycle_branch_policy();
+    }
+
     if is_key_pressed(KeyCode::F3) {
         state.debug_draw_config.draw_paths = !state.debug_draw_config.draw_paths;
     }
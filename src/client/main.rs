@@ -1,32 +1,41 @@
+use building_menu::draw_building_menu;
 use client_game_state::ClientGameState;
 use common::component_attack::{Attack, AttackVariant};
 use common::component_movement::get_detection_range;
-use common::draw::{draw_card, draw_progress_bar, draw_rect_transform};
+use common::draw::{
+    draw_card, draw_progress_bar, draw_rect_transform, draw_text_with_origin, TextOriginX,
+    TextOriginY,
+};
 use common::draw_server_controlled_game_state::{draw_minimap, draw_server_controlled_game_state};
 use common::game_state::{DynamicGameState, ServerControlledGameState, StaticGameState};
 use common::get_unit_spawnpoints::get_unit_spawnpoints;
 use common::ids::{EntityId, PlayerId};
 use common::network::ClientMessage;
+use common::path_graph::BranchPolicy;
 use common::play_target::{
-    unit_spawnpoint_target_transform, BuildingLocationTarget, PlayFn, TargetIsInvalidArgs,
+    get_path_node_targets, unit_spawnpoint_target_transform, BuildingLocationTarget,
+    PathNodeTarget, PlayFn, TargetIsInvalidArgs,
 };
 use common::rect_transform::{point_inside, RectTransform};
 use common::sprite_id::SpriteId;
 use common::sprites::Sprites;
 use common::view_state::{get_level_aspect, get_level_rect, get_screen_aspect, ViewState};
-use common::world::{find_entity, BuildingLocation, Zoning};
+use common::world::{find_entity, get_path_pos, BuildingLocation, Zoning};
 use input::main_input;
 use itertools::Itertools;
-use macroquad::color::{Color, BLACK, BLUE, RED, WHITE, YELLOW};
+use macroquad::color::{Color, BLACK, BLUE, GOLD, RED, WHITE, YELLOW};
 use macroquad::input::is_key_pressed;
 use macroquad::math::{Rect, Vec2};
 use macroquad::miniquad::KeyCode;
-use macroquad::shapes::{draw_circle, draw_circle_lines, draw_poly_lines};
+use macroquad::shapes::{draw_circle, draw_circle_lines, draw_line, draw_poly_lines};
+use macroquad::text::Font;
 use macroquad::texture::draw_texture;
 use macroquad::window::{clear_background, screen_height, screen_width};
 use macroquad::{window::next_frame, window::request_new_screen_size};
+use network::Handshake;
 use physical_hand::{hand_step, hand_sync, PhysicalHand};
 use text_box::TextBox;
+mod building_menu;
 mod client_game_state;
 pub mod config;
 mod deck_builder;
@@ -60,36 +69,45 @@ async fn main() {
 
             next_frame().await;
 
-            if is_key_pressed(KeyCode::Enter) {
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::F2) {
                 state.client_network_state.server_addr = text_box
                     .text
                     .parse()
                     .unwrap_or(state.client_network_state.server_addr);
                 state.deck_builder.save();
+                state.client_network_state.handshake = Handshake::Pending;
+                state.spectating = is_key_pressed(KeyCode::F2);
                 state.in_deck_builder = false;
             }
         } else {
-            state
-                .client_network_state
-                .ensure_joined(ClientMessage::JoinGame(
+            let join_message = if state.spectating {
+                ClientMessage::SpectateGame
+            } else {
+                ClientMessage::JoinGame(
                     state
                         .deck_builder
                         .deck
                         .iter()
                         .map(|physical_card| physical_card.card.clone())
                         .collect_vec(),
-                ));
+                )
+            };
+            state.client_network_state.ensure_joined(join_message);
             while let Some(server_message) = state.client_network_state.receive() {
                 state
                     .server_controlled_game_state
                     .update_with_server_message(server_message);
             }
-            if !state.has_player() {
-                return;
+            if let Handshake::Rejected(reason) = &state.client_network_state.handshake {
+                draw_handshake_error(&mut state.view_state, reason, &state.font);
+            } else {
+                if !state.has_player() && !state.spectating {
+                    return;
+                }
+                main_step(&mut state);
+                state.client_network_state.send_queued();
+                draw_client_game_state(&mut state);
             }
-            main_step(&mut state);
-            state.client_network_state.send_queued();
-            draw_client_game_state(&mut state);
 
             next_frame().await;
 
@@ -103,7 +121,9 @@ async fn main() {
 fn main_step(state: &mut ClientGameState) {
     state.step();
     main_input(state);
-    hand_sync(state);
+    if state.has_player() {
+        hand_sync(state);
+    }
     hand_step(state);
     state.hit_numbers.step(
         &state
@@ -186,6 +206,67 @@ fn draw_building_location_play_targets(
     }
 }
 
+fn draw_path_node_play_targets(
+    mouse_world_pos: Vec2,
+    server_controlled_game_state: &ServerControlledGameState,
+    physical_hand: &PhysicalHand,
+    player_id: PlayerId,
+) {
+    let Some(PlayFn::PathNode(specific_play_fn)) = physical_hand
+        .card_idx_being_held
+        .and_then(|idx| physical_hand.cards.get(idx))
+        .map(|physical_card| physical_card.card_instance.card.get_card_data().play_fn)
+    else {
+        return;
+    };
+    let static_game_state = &server_controlled_game_state.static_game_state;
+    for target in get_path_node_targets(static_game_state) {
+        if specific_play_fn.target_is_invalid(TargetIsInvalidArgs::<PathNodeTarget> {
+            target: &target,
+            owner: player_id,
+            static_game_state,
+            semi_static_game_state: &server_controlled_game_state.semi_static_game_state,
+            dynamic_game_state: &server_controlled_game_state.dynamic_game_state,
+        }) {
+            continue;
+        }
+        let pos = get_path_pos(static_game_state, target.path_id, target.path_idx);
+        let radius = 20.0;
+        let hovering = (mouse_world_pos - pos).length() < radius;
+        let color = Color {
+            a: if hovering { 0.8 } else { 0.5 },
+            ..RED
+        };
+        draw_poly_lines(pos.x, pos.y, 6, radius, 0., 3.0, color);
+    }
+}
+
+fn draw_area_radius_preview(
+    mouse_world_pos: Vec2,
+    static_game_state: &StaticGameState,
+    physical_hand: &PhysicalHand,
+) {
+    let Some(radius) = physical_hand
+        .card_idx_being_held
+        .and_then(|idx| physical_hand.cards.get(idx))
+        .and_then(|physical_card| {
+            physical_card
+                .card_instance
+                .card
+                .get_area_radius(static_game_state)
+        })
+    else {
+        return;
+    };
+    draw_circle(
+        mouse_world_pos.x,
+        mouse_world_pos.y,
+        radius,
+        Color { a: 0.2, ..WHITE },
+    );
+    draw_circle_lines(mouse_world_pos.x, mouse_world_pos.y, radius, 3.0, WHITE);
+}
+
 fn draw_range_circle_preview(
     dynamic_game_state: &DynamicGameState,
     selected_entity_id: Option<EntityId>,
@@ -277,11 +358,106 @@ fn draw_progress_bars(state: &ClientGameState) {
     }
 }
 
+/// Lists the modifiers on the player's economy above the progress bars
+fn draw_player_modifiers(state: &ClientGameState) {
+    let Some(player) = state
+        .server_controlled_game_state
+        .dynamic_game_state
+        .players
+        .get(&state.player_id)
+    else {
+        return;
+    };
+    let bottom = screen_height() - 100.0 - 2.0 * 10.0;
+    for (idx, line) in player.modifiers.describe().iter().rev().enumerate() {
+        draw_text_with_origin(
+            line,
+            screen_width() - 10.0,
+            bottom - idx as f32 * 22.0,
+            20.0,
+            0.0,
+            WHITE,
+            TextOriginX::Right,
+            TextOriginY::Bottom,
+            Some(&state.font),
+        );
+    }
+}
+
+fn draw_spectator_label(state: &ClientGameState) {
+    let color = state
+        .server_controlled_game_state
+        .dynamic_game_state
+        .players
+        .get(&state.player_id)
+        .map_or(WHITE, |player| player.color);
+    draw_text_with_origin(
+        "Spectating [Tab]",
+        screen_width() - 10.0,
+        10.0,
+        28.0,
+        0.0,
+        color,
+        TextOriginX::Right,
+        TextOriginY::Top,
+        Some(&state.font),
+    );
+}
+
+fn draw_game_over_label(state: &ClientGameState) {
+    let dynamic_game_state = &state.server_controlled_game_state.dynamic_game_state;
+    let Some(winning_team) = dynamic_game_state.get_winning_team() else {
+        return;
+    };
+    let Some(player) = dynamic_game_state.players.get(&state.player_id) else {
+        return;
+    };
+    let (text, color) = if player.team == winning_team {
+        ("Victory", GOLD)
+    } else {
+        ("Defeat", RED)
+    };
+    draw_text_with_origin(
+        text,
+        screen_width() / 2.0,
+        screen_height() / 3.0,
+        64.0,
+        0.0,
+        color,
+        TextOriginX::Center,
+        TextOriginY::Center,
+        Some(&state.font),
+    );
+}
+
+fn draw_handshake_error(view_state: &mut ViewState, reason: &str, font: &Font) {
+    view_state.set_ui_overlay_camera();
+    clear_background(BLACK);
+    let font_size = 28.0;
+    let lines = reason.lines().chain(["", "[Esc] Back"]).collect_vec();
+    let y_start = (screen_height() - lines.len() as f32 * font_size) / 2.0;
+    for (i, line) in lines.iter().enumerate() {
+        draw_text_with_origin(
+            line,
+            screen_width() / 2.0,
+            y_start + i as f32 * font_size,
+            font_size,
+            0.0,
+            WHITE,
+            TextOriginX::Center,
+            TextOriginY::Top,
+            Some(font),
+        );
+    }
+}
+
 fn draw_spawnpoint_play_targets(
     mouse_world_pos: Vec2,
     player_id: PlayerId,
+    branch_policy: &BranchPolicy,
     static_game_state: &StaticGameState,
     dynamic_game_state: &DynamicGameState,
+    font: &Font,
 ) {
     let unit_spawnpoint_targets =
         get_unit_spawnpoints(player_id, static_game_state, dynamic_game_state);
@@ -295,6 +471,33 @@ fn draw_spawnpoint_play_targets(
                 ..RED
             },
         );
+        if !hovering {
+            continue;
+        }
+        let route = match branch_policy {
+            BranchPolicy::FixedLane(lane) => {
+                if let Some(path) = static_game_state.paths.get(lane) {
+                    for ((x1, y1), (x2, y2)) in path.iter().tuple_windows() {
+                        draw_line(*x1, *y1, *x2, *y2, 6.0, RED);
+                    }
+                }
+                "Fixed lane"
+            }
+            BranchPolicy::ShortestToEnemyBase => "Shortest",
+            BranchPolicy::LeastDefended => "Least defended",
+            BranchPolicy::Towards(_) => "Towards",
+        };
+        draw_text_with_origin(
+            &format!("Route: {} [R]", route),
+            transform.x,
+            transform.y - transform.h / 2.0,
+            20.0,
+            0.0,
+            WHITE,
+            TextOriginX::Center,
+            TextOriginY::Bottom,
+            Some(font),
+        );
     }
 }
 
@@ -306,9 +509,10 @@ fn draw_client_game_state(state: &mut ClientGameState) {
         h: 1.0,
     };
 
-    state
-        .view_state
-        .set_scrolling_level_camera(level_display_space);
+    state.view_state.set_scrolling_level_camera(
+        &state.server_controlled_game_state.static_game_state,
+        level_display_space,
+    );
     clear_background(BLACK);
     let map_texture = state.sprites.get_texture(&SpriteId::Map);
     draw_texture(map_texture, 0.0, 0.0, WHITE);
@@ -316,10 +520,16 @@ fn draw_client_game_state(state: &mut ClientGameState) {
     state.view_state.set_ui_overlay_camera();
     draw_physical_hand(&state.physical_hand, &state.sprites);
     draw_progress_bars(state);
+    draw_player_modifiers(state);
+    if state.spectating {
+        draw_spectator_label(state);
+    }
+    draw_game_over_label(state);
 
-    state
-        .view_state
-        .set_scrolling_level_camera(level_display_space);
+    state.view_state.set_scrolling_level_camera(
+        &state.server_controlled_game_state.static_game_state,
+        level_display_space,
+    );
     draw_server_controlled_game_state(
         &state.server_controlled_game_state,
         &state.sprites,
@@ -329,28 +539,46 @@ fn draw_client_game_state(state: &mut ClientGameState) {
         &state.server_controlled_game_state.dynamic_game_state,
         state.selected_entity_id,
     );
+    draw_building_menu(state);
+    draw_area_radius_preview(
+        state.view_state.get_mouse_world_pos(),
+        &state.server_controlled_game_state.static_game_state,
+        &state.physical_hand,
+    );
     draw_building_location_play_targets(
         state.view_state.get_mouse_world_pos(),
         &state.server_controlled_game_state,
         &state.physical_hand,
         state.player_id,
     );
-    draw_spawnpoint_play_targets(
+    draw_path_node_play_targets(
         state.view_state.get_mouse_world_pos(),
+        &state.server_controlled_game_state,
+        &state.physical_hand,
         state.player_id,
-        &state.server_controlled_game_state.static_game_state,
-        &state.server_controlled_game_state.dynamic_game_state,
     );
+    if !state.spectating {
+        draw_spawnpoint_play_targets(
+            state.view_state.get_mouse_world_pos(),
+            state.player_id,
+            &state.branch_policy,
+            &state.server_controlled_game_state.static_game_state,
+            &state.server_controlled_game_state.dynamic_game_state,
+            &state.font,
+        );
+    }
 
     state.hit_numbers.draw(Some(&state.font));
 
-    let level_rect = get_level_rect();
+    let static_game_state = &state.server_controlled_game_state.static_game_state;
+    let level_rect = get_level_rect(static_game_state);
 
     let minimap_display_space = Rect {
         x: 0.0,
         y: 0.0,
         w: state.view_state.ui_bar_width,
-        h: state.view_state.ui_bar_width * get_screen_aspect() / get_level_aspect(),
+        h: state.view_state.ui_bar_width * get_screen_aspect()
+            / get_level_aspect(static_game_state),
     };
     ViewState::set_camera(level_rect, minimap_display_space);
     let minimap_texture = state.sprites.get_texture(&SpriteId::Minimap);
@@ -2,7 +2,8 @@ use crate::config::default_server_addr;
 use common::{
     ids::PlayerId,
     message_acknowledgement::AckUdpSocket,
-    network::{hash_client_addr, ClientMessage, ServerMessage},
+    network::{hash_client_addr, ClientMessage, ProtocolHello, ServerMessage, ServerMessageData},
+    network_simulation::NetworkConditions,
 };
 use local_ip_address::local_ip;
 use std::{
@@ -10,8 +11,15 @@ use std::{
     time::SystemTime,
 };
 
+pub enum Handshake {
+    Pending,
+    Accepted,
+    Rejected(String),
+}
+
 pub struct ClientNetworkState {
     pub server_addr: SocketAddr,
+    pub handshake: Handshake,
     ack_udp_socket: AckUdpSocket<ClientMessage, ServerMessage>,
     last_server_com: Option<SystemTime>,
 }
@@ -26,10 +34,13 @@ impl ClientNetworkState {
             })
             .unwrap();
         udp_socket.set_nonblocking(true).unwrap();
+        let mut ack_udp_socket = AckUdpSocket::new(udp_socket, std::time::Duration::from_secs(1));
+        ack_udp_socket.simulate_network_conditions(NetworkConditions::from_args());
 
         Self {
             server_addr: default_server_addr(),
-            ack_udp_socket: AckUdpSocket::new(udp_socket, std::time::Duration::from_secs(1)),
+            handshake: Handshake::Pending,
+            ack_udp_socket,
             last_server_com: None,
         }
     }
@@ -41,9 +52,14 @@ impl ClientNetworkState {
         {
             return;
         }
+        let message = match self.handshake {
+            Handshake::Pending => ClientMessage::Hello(ProtocolHello::current()),
+            Handshake::Accepted => join_message,
+            Handshake::Rejected(_) => return,
+        };
         self.last_server_com = Some(SystemTime::now());
         self.ack_udp_socket
-            .send_to(join_message, &self.server_addr, false); // No ack needed: If the server doesn't respond, we'll just try again
+            .send_to(message, &self.server_addr, false); // No ack needed: If the server doesn't respond, we'll just try again
     }
 
     pub fn push_command(&mut self, client_message: ClientMessage) {
@@ -56,7 +72,18 @@ impl ClientNetworkState {
         if response.is_some() {
             self.last_server_com = Some(SystemTime::now());
         }
-        response
+        match response.as_ref().map(|message| &message.data) {
+            Some(ServerMessageData::HelloAccepted) => {
+                self.handshake = Handshake::Accepted;
+                self.last_server_com = None; // Join right away
+                self.receive()
+            }
+            Some(ServerMessageData::HelloRejected(reason)) => {
+                self.handshake = Handshake::Rejected(reason.clone());
+                self.receive()
+            }
+            _ => response,
+        }
     }
 
     pub fn send_queued(&mut self) {
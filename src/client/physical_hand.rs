@@ -11,10 +11,11 @@ use common::{
     get_unit_spawnpoints::get_unit_spawnpoints,
     network::ClientMessage,
     play_target::{
-        unit_spawnpoint_target_transform, BuildingLocationTarget, EntityTarget, PlayFn, PlayTarget,
-        WorldPosTarget,
+        get_path_node_targets, unit_spawnpoint_target_transform, BuildingLocationTarget,
+        EntityTarget, PlayFn, PlayTarget, UnitSpawnpointTarget, WorldPosTarget,
     },
     rect_transform::point_inside,
+    world::get_path_pos,
 };
 use macroquad::{
     input::{is_mouse_button_pressed, is_mouse_button_released},
@@ -54,9 +55,7 @@ pub fn hand_sync(state: &mut ClientGameState) {
 }
 
 pub fn hand_try_play(state: &ClientGameState) -> Option<CardInstance> {
-    let Some(card_idx_being_held) = state.physical_hand.card_idx_being_held else {
-        return None;
-    };
+    let card_idx_being_held = state.physical_hand.card_idx_being_held?;
     let card_instance = state
         .physical_hand
         .cards
@@ -64,7 +63,8 @@ pub fn hand_try_play(state: &ClientGameState) -> Option<CardInstance> {
         .unwrap()
         .card_instance
         .clone();
-    if state.get_player().hand.energy < card_instance.card.get_card_data().energy_cost {
+    let player = state.get_player();
+    if player.hand.energy < player.modifiers.energy_cost(&card_instance.card) {
         return None;
     }
     Some(card_instance)
@@ -126,7 +126,10 @@ pub fn hand_step(state: &mut ClientGameState) {
                                 .client_network_state
                                 .push_command(ClientMessage::PlayCard(
                                     card_instance.id,
-                                    PlayTarget::UnitSpawnpoint(target.clone()),
+                                    PlayTarget::UnitSpawnpoint(UnitSpawnpointTarget {
+                                        branch_policy: state.branch_policy.clone(),
+                                        ..target.clone()
+                                    }),
                                 ));
                         }
                     }
@@ -154,6 +157,33 @@ pub fn hand_step(state: &mut ClientGameState) {
                         }
                     }
                 }
+                PlayFn::PathNode(_) => {
+                    let static_game_state = &state.server_controlled_game_state.static_game_state;
+                    if let Some(target) =
+                        get_path_node_targets(static_game_state)
+                            .into_iter()
+                            .find(|target| {
+                                let r = 20.0;
+                                (mouse_world_position
+                                    - get_path_pos(
+                                        static_game_state,
+                                        target.path_id,
+                                        target.path_idx,
+                                    ))
+                                .length()
+                                    < r
+                            })
+                    {
+                        if let Some(card_instance) = hand_try_play(state) {
+                            state
+                                .client_network_state
+                                .push_command(ClientMessage::PlayCard(
+                                    card_instance.id,
+                                    PlayTarget::PathNode(target),
+                                ));
+                        }
+                    }
+                }
                 PlayFn::Entity(_) => {
                     if let Some(entity) = state
                         .server_controlled_game_state
@@ -188,7 +218,7 @@ pub fn hand_step(state: &mut ClientGameState) {
             );
             if point_inside(mouse_screen_pos_vec(), &in_hand_transform) {
                 top_hovering_card_idx = Some(i);
-                if is_mouse_button_pressed(MouseButton::Left) {
+                if is_mouse_button_pressed(MouseButton::Left) && !state.spectating {
                     state.physical_hand.card_idx_being_held = Some(i);
                 }
             }
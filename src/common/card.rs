@@ -1,14 +1,19 @@
 use crate::{
     buff::{buff_add_to_entity, ArithmeticBuff, Buff, ExtraHealthBuff},
-    component_attack::AttackVariant,
-    entity::{EntityState, EntityTag},
+    component_attack::{AttackVariant, DamageType, TargetPool},
+    component_status_effects::StatusEffect,
+    entity::{AbilityFlag, EntityState, EntityTag},
     entity_blueprint::EntityBlueprint,
+    game_state::StaticGameState,
     ids::CardInstanceId,
-    level_config::get_prototype_level_config,
     play_target::{
         EntityTarget, PlayArgs, PlayFn, SpecificPlayFn, TargetIsInvalidArgs, WorldPosTarget,
     },
-    world::{find_entity, find_entity_mut, world_place_builder, world_place_path_entity, Zoning},
+    player_modifier::{PlayerModifier, TimedPlayerModifier},
+    world::{
+        find_entity, find_entity_mut, get_path_pos, world_place_builder, world_place_path_entity,
+        world_place_trap, world_place_zone, Zoning,
+    },
 };
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
@@ -19,13 +24,20 @@ pub enum Card {
     Tower,
     SmallTower,
     Watchtower,
+    Catapult,
+    CannonTower,
     Wall,
+    Shrine,
+    Barracks,
+    GoblinHut,
     Farm,
     TradingPlace,
     IronMine,
     SpawnPoint,
     HomesickWarrior,
+    ShieldBearer,
     ElfWarrior,
+    VenomArcher,
     OldSwordMaster,
     DemonWolf,
     SmallCriminal,
@@ -34,13 +46,23 @@ pub enum Card {
     RecklessKnight,
     Governor,
     DynamiteMan,
+    Priest,
+    PlagueRat,
     WarEagle,
     AirBalloon,
     Dragon,
     DirectDamage,
     LightningStrike,
+    FrostNova,
+    BurningGround,
+    TarPit,
+    HolyCircle,
+    SpikeTrap,
+    SnareTrap,
+    StunTrap,
     ReinforcedDoors,
     HigherMotivation,
+    Recruitment,
     SteadyAim,
     Meteor,
     BlackDeath,
@@ -50,6 +72,37 @@ impl Card {
     pub fn iter() -> impl Iterator<Item = Card> {
         <Card as IntoEnumIterator>::iter()
     }
+
+    /// Radius of the area affected around the position the card is played at
+    pub fn get_area_radius(&self, static_game_state: &StaticGameState) -> Option<f32> {
+        match self {
+            Card::LightningStrike | Card::FrostNova => Some(static_game_state.nearby_radius),
+            Card::BurningGround => Some(EntityBlueprint::BurningGround.create().radius),
+            Card::TarPit => Some(EntityBlueprint::TarPit.create().radius),
+            Card::HolyCircle => Some(EntityBlueprint::HolyCircle.create().radius),
+            _ => None,
+        }
+    }
+}
+
+/// Kinds of cards, by what they are played on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CardTag {
+    Building,
+    Unit,
+    Spell,
+    Trap,
+}
+
+impl Card {
+    pub fn get_tag(&self) -> CardTag {
+        match self.get_card_data().play_fn {
+            PlayFn::BuildingLocation(_) => CardTag::Building,
+            PlayFn::UnitSpawnPoint(_) => CardTag::Unit,
+            PlayFn::WorldPos(_) | PlayFn::Entity(_) => CardTag::Spell,
+            PlayFn::PathNode(_) => CardTag::Trap,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -79,17 +132,65 @@ macro_rules! play_normal_building {
                     play_args.target,
                     EntityBlueprint::$builder_blueprint.create(),
                     EntityBlueprint::$building_blueprint,
+                    Card::$building_blueprint,
                     play_args.owner,
                 )
             })
             .with_target_is_invalid(|play_args| {
-                play_args
+                let building_location = play_args
                     .semi_static_game_state
                     .building_locations()
                     .get(&play_args.target.id)
-                    .unwrap()
-                    .zoning
-                    != Zoning::Normal
+                    .unwrap();
+                building_location.zoning != Zoning::Normal || !building_location.is_available()
+            }),
+        )
+    };
+}
+
+macro_rules! play_zone {
+    ($zone_blueprint:ident) => {
+        PlayFn::WorldPos(SpecificPlayFn::new(|play_args| {
+            world_place_zone(
+                play_args.dynamic_game_state,
+                EntityBlueprint::$zone_blueprint.create(),
+                play_args.owner,
+                play_args.target.to_vec2(),
+            );
+            true
+        }))
+    };
+}
+
+macro_rules! play_trap {
+    ($trap_blueprint:ident) => {
+        PlayFn::PathNode(
+            SpecificPlayFn::new(|play_args| {
+                world_place_trap(
+                    play_args.static_game_state,
+                    play_args.dynamic_game_state,
+                    play_args.target,
+                    EntityBlueprint::$trap_blueprint.create(),
+                    play_args.owner,
+                );
+                true
+            })
+            .with_target_is_invalid(|play_args| {
+                // Only the player's own traps are checked, since enemy traps are hidden
+                let pos = get_path_pos(
+                    play_args.static_game_state,
+                    play_args.target.path_id,
+                    play_args.target.path_idx,
+                );
+                play_args
+                    .dynamic_game_state
+                    .entities
+                    .iter()
+                    .any(|entity_instance| {
+                        entity_instance.entity.tag == EntityTag::Trap
+                            && entity_instance.owner == play_args.owner
+                            && entity_instance.pos == pos
+                    })
             }),
         )
     };
@@ -106,17 +207,17 @@ macro_rules! play_commerce_building {
                     play_args.target,
                     EntityBlueprint::$builder_blueprint.create(),
                     EntityBlueprint::$building_blueprint,
+                    Card::$building_blueprint,
                     play_args.owner,
                 )
             })
             .with_target_is_invalid(|play_args| {
-                play_args
+                let building_location = play_args
                     .semi_static_game_state
                     .building_locations()
                     .get(&play_args.target.id)
-                    .unwrap()
-                    .zoning
-                    != Zoning::Commerce
+                    .unwrap();
+                building_location.zoning != Zoning::Commerce || !building_location.is_available()
             }),
         )
     };
@@ -165,7 +266,25 @@ impl Card {
                 card_art_path: "watchtower.jpg",
                 attack: EntityBlueprint::Watchtower.get_attack(),
                 health: EntityBlueprint::Watchtower.get_health(),
-                description: "[Ranged] Nearby ranged attacks have double range",
+                description: "[Ranged] Nearby ranged attacks have double range. Reveals hidden enemies nearby",
+            },
+            Card::Catapult => CardData {
+                name: "Catapult",
+                energy_cost: 4,
+                play_fn: play_normal_building!(BasicBuilder, Catapult),
+                card_art_path: "catapult.jpg",
+                attack: EntityBlueprint::Catapult.get_attack(),
+                health: EntityBlueprint::Catapult.get_health(),
+                description: "[Long range] Hurls boulders\nthat damage every unit\nwhere they land",
+            },
+            Card::CannonTower => CardData {
+                name: "Cannon Tower",
+                energy_cost: 4,
+                play_fn: play_normal_building!(BasicBuilder, CannonTower),
+                card_art_path: "cannon_tower.jpg",
+                attack: EntityBlueprint::CannonTower.get_attack(),
+                health: EntityBlueprint::CannonTower.get_health(),
+                description: "[Ranged] Cannonballs pass\nthrough 2 more units",
             },
             Card::Wall => CardData {
                 name: "Wall",
@@ -174,7 +293,35 @@ impl Card {
                 card_art_path: "wall.jpg",
                 attack: EntityBlueprint::Wall.get_attack(),
                 health: EntityBlueprint::Wall.get_health(),
-                description: "",
+                description: "[Armored]",
+            },
+            Card::Shrine => CardData {
+                name: "Shrine",
+                energy_cost: 3,
+                play_fn: play_normal_building!(BasicBuilder, Shrine),
+                card_art_path: "shrine.jpg",
+                attack: EntityBlueprint::Shrine.get_attack(),
+                health: EntityBlueprint::Shrine.get_health(),
+                description: "Nearby units you control\nregenerate 10 health\nper second",
+            },
+            Card::Barracks => CardData {
+                name: "Barracks",
+                energy_cost: 5,
+                play_fn: play_normal_building!(BasicBuilder, Barracks),
+                card_art_path: "barracks.jpg",
+                attack: EntityBlueprint::Barracks.get_attack(),
+                health: EntityBlueprint::Barracks.get_health(),
+                description:
+                    "Trains a Street Criminal\nevery 10 seconds, up to 3.\nPaused while damaged",
+            },
+            Card::GoblinHut => CardData {
+                name: "Goblin Hut",
+                energy_cost: 4,
+                play_fn: play_normal_building!(BasicBuilder, GoblinHut),
+                card_art_path: "goblin_hut.jpg",
+                attack: EntityBlueprint::GoblinHut.get_attack(),
+                health: EntityBlueprint::GoblinHut.get_health(),
+                description: "Spawns a Small Criminal\nevery 5 seconds, up to 4",
             },
             Card::Farm => CardData {
                 name: "Farm",
@@ -221,6 +368,15 @@ impl Card {
                 health: EntityBlueprint::HomesickWarrior.get_health(),
                 description: "[Protector]",
             },
+            Card::ShieldBearer => CardData {
+                name: "Shield Bearer",
+                energy_cost: 3,
+                play_fn: play_unit!(ShieldBearer),
+                card_art_path: "shield_bearer.jpg",
+                attack: EntityBlueprint::ShieldBearer.get_attack(),
+                health: EntityBlueprint::ShieldBearer.get_health(),
+                description: "[Slow moving], [Armored]\nAllies can not\nwalk past this unit",
+            },
             Card::ElfWarrior => CardData {
                 name: "Elf Warrior",
                 energy_cost: 2,
@@ -228,7 +384,16 @@ impl Card {
                 card_art_path: "elf_warrior.jpg",
                 attack: EntityBlueprint::ElfWarrior.get_attack(),
                 health: EntityBlueprint::ElfWarrior.get_health(),
-                description: "[Fast attacking], [Ranged]",
+                description: "[Fast attacking], [Ranged]\n[Piercing]\n+50% damage to flying",
+            },
+            Card::VenomArcher => CardData {
+                name: "Venom Archer",
+                energy_cost: 3,
+                play_fn: play_unit!(VenomArcher),
+                card_art_path: "archer.jpg",
+                attack: EntityBlueprint::VenomArcher.get_attack(),
+                health: EntityBlueprint::VenomArcher.get_health(),
+                description: "[Ranged]\nPoisons its targets",
             },
             Card::OldSwordMaster => CardData {
                 name: "Old Sword Master",
@@ -237,7 +402,7 @@ impl Card {
                 card_art_path: "old_sword_master.jpg",
                 attack: EntityBlueprint::OldSwordMaster.get_attack(),
                 health: EntityBlueprint::OldSwordMaster.get_health(),
-                description: "[Very slow moving]",
+                description: "[Very slow moving]\n[Immune against spells]",
             },
             Card::DemonWolf => CardData {
                 name: "Demon Wolf",
@@ -300,7 +465,25 @@ impl Card {
                 card_art_path: "dynamite_man.jpg",
                 attack: EntityBlueprint::DynamiteMan.get_attack(),
                 health: EntityBlueprint::DynamiteMan.get_health(),
-                description: "Explodes!",
+                description: "Explodes!\n[Siege]\nTriple damage to buildings",
+            },
+            Card::Priest => CardData {
+                name: "Priest",
+                energy_cost: 2,
+                play_fn: play_unit!(Priest),
+                card_art_path: "priest.jpg",
+                attack: EntityBlueprint::Priest.get_attack(),
+                health: EntityBlueprint::Priest.get_health(),
+                description: "[Ranged]\nHeals the most\ndamaged nearby ally",
+            },
+            Card::PlagueRat => CardData {
+                name: "Plague Rat",
+                energy_cost: 3,
+                play_fn: play_unit!(PlagueRat),
+                card_art_path: "plague_rat.jpg",
+                attack: EntityBlueprint::PlagueRat.get_attack(),
+                health: EntityBlueprint::PlagueRat.get_health(),
+                description: "[Fast moving]\nNearby enemy units\ntake 5 damage per second",
             },
             Card::WarEagle => CardData {
                 name: "War Eagle",
@@ -327,21 +510,41 @@ impl Card {
                 card_art_path: "dragon.jpg",
                 attack: EntityBlueprint::Dragon.get_attack(),
                 health: EntityBlueprint::Dragon.get_health(),
-                description: "[Flying]",
+                description: "[Flying], [Magic resistant]",
             },
             Card::DirectDamage => CardData {
                 name: "Direct Damage",
                 energy_cost: 1,
-                play_fn: PlayFn::Entity(SpecificPlayFn::new(|play_args| {
-                    let Some(target_entity_instance) = find_entity_mut(
-                        &mut play_args.dynamic_game_state.entities,
-                        Some(play_args.target.id),
-                    ) else {
-                        return false;
-                    };
-                    target_entity_instance.entity.health.deal_damage(150.0);
-                    true
-                })),
+                play_fn: PlayFn::Entity(
+                    SpecificPlayFn::new(|play_args: PlayArgs<EntityTarget>| {
+                        let Some(target_entity_instance) = find_entity_mut(
+                            &mut play_args.dynamic_game_state.entities,
+                            Some(play_args.target.id),
+                        ) else {
+                            return false;
+                        };
+                        if target_entity_instance
+                            .entity
+                            .has_ability(AbilityFlag::SpellImmune)
+                        {
+                            return false;
+                        }
+                        target_entity_instance
+                            .entity
+                            .health
+                            .deal_typed_damage(150.0, &DamageType::Magic);
+                        true
+                    })
+                    .with_target_is_invalid(
+                        |play_args: TargetIsInvalidArgs<EntityTarget>| {
+                            find_entity(
+                                &play_args.dynamic_game_state.entities,
+                                Some(play_args.target.id),
+                            )
+                            .is_none_or(|e| e.entity.has_ability(AbilityFlag::SpellImmune))
+                        },
+                    ),
+                ),
                 card_art_path: "direct_damage.jpg",
                 attack: None,
                 health: None,
@@ -352,10 +555,15 @@ impl Card {
                 energy_cost: 3,
                 play_fn: PlayFn::WorldPos(SpecificPlayFn::new(|play_args| {
                     for entity_instance in play_args.dynamic_game_state.entities.iter_mut() {
-                        if entity_instance.pos.distance(play_args.target.to_vec2())
-                            < get_prototype_level_config().nearby_radius
+                        if !matches!(entity_instance.entity.tag, EntityTag::Zone | EntityTag::Trap)
+                            && !entity_instance.entity.has_ability(AbilityFlag::SpellImmune)
+                            && entity_instance.pos.distance(play_args.target.to_vec2())
+                                < play_args.static_game_state.nearby_radius
                         {
-                            entity_instance.entity.health.deal_damage(150.0);
+                            entity_instance
+                                .entity
+                                .health
+                                .deal_typed_damage(150.0, &DamageType::Magic);
                         }
                     }
                     true
@@ -365,6 +573,91 @@ impl Card {
                 health: None,
                 description: "Deal 150 damage\nto all units and buildings\nin a small area",
             },
+            Card::FrostNova => CardData {
+                name: "Frost Nova",
+                energy_cost: 3,
+                play_fn: PlayFn::WorldPos(SpecificPlayFn::new(|play_args| {
+                    let players = &play_args.dynamic_game_state.players;
+                    for entity_instance in play_args.dynamic_game_state.entities.iter_mut() {
+                        if matches!(
+                            entity_instance.entity.tag,
+                            EntityTag::Unit | EntityTag::FlyingUnit
+                        ) && TargetPool::Enemies.in_pool(
+                            players,
+                            play_args.owner,
+                            entity_instance.owner,
+                        )
+                            && !entity_instance.entity.has_ability(AbilityFlag::SpellImmune)
+                            && entity_instance.pos.distance(play_args.target.to_vec2())
+                                < play_args.static_game_state.nearby_radius
+                        {
+                            entity_instance
+                                .entity
+                                .status_effects
+                                .apply(StatusEffect::stun(3.0));
+                        }
+                    }
+                    true
+                })),
+                card_art_path: "frost_nova.jpg",
+                attack: None,
+                health: None,
+                description: "Stun enemy units\nin a small area\nfor 3 seconds",
+            },
+            Card::BurningGround => CardData {
+                name: "Burning Ground",
+                energy_cost: 3,
+                play_fn: play_zone!(BurningGround),
+                card_art_path: "burning_ground.jpg",
+                attack: None,
+                health: None,
+                description: "Set an area on fire,\nburning enemy units\nfor 8 seconds",
+            },
+            Card::TarPit => CardData {
+                name: "Tar Pit",
+                energy_cost: 2,
+                play_fn: play_zone!(TarPit),
+                card_art_path: "tar_pit.jpg",
+                attack: None,
+                health: None,
+                description: "Enemy units in the area\nare slowed by 60%\nfor 10 seconds",
+            },
+            Card::HolyCircle => CardData {
+                name: "Holy Circle",
+                energy_cost: 3,
+                play_fn: play_zone!(HolyCircle),
+                card_art_path: "holy_circle.jpg",
+                attack: None,
+                health: None,
+                description: "Your units in the area\nheal 15 health per second\nfor 10 seconds",
+            },
+            Card::SpikeTrap => CardData {
+                name: "Spike Trap",
+                energy_cost: 2,
+                play_fn: play_trap!(SpikeTrap),
+                card_art_path: "spike_trap.jpg",
+                attack: Some(150),
+                health: None,
+                description: "Hidden trap on the path,\ndeals 150 damage to\nthe first enemy unit",
+            },
+            Card::SnareTrap => CardData {
+                name: "Snare Trap",
+                energy_cost: 1,
+                play_fn: play_trap!(SnareTrap),
+                card_art_path: "snare_trap.jpg",
+                attack: None,
+                health: None,
+                description: "Hidden trap on the path,\nroots the first enemy unit\nfor 5 seconds",
+            },
+            Card::StunTrap => CardData {
+                name: "Stun Trap",
+                energy_cost: 3,
+                play_fn: play_trap!(StunTrap),
+                card_art_path: "stun_trap.jpg",
+                attack: None,
+                health: None,
+                description: "Hidden trap on the path,\nstuns nearby enemy units\nfor 2 seconds",
+            },
             Card::ReinforcedDoors => CardData {
                 name: "Reinforced Doors",
                 energy_cost: 2,
@@ -409,6 +702,25 @@ impl Card {
                 attack: None,
                 health: None,
             },
+            Card::Recruitment => CardData {
+                name: "Recruitment",
+                energy_cost: 2,
+                play_fn: PlayFn::WorldPos(SpecificPlayFn::new(|play_args| {
+                    let Some(player) = play_args.dynamic_game_state.players.get_mut(&play_args.owner)
+                    else {
+                        return false;
+                    };
+                    player.timed_modifiers.push(TimedPlayerModifier::new(
+                        PlayerModifier::CardCost(CardTag::Unit, -1),
+                        30.0,
+                    ));
+                    true
+                })),
+                description: "Your unit cards\ncost 1 less for 30 seconds",
+                card_art_path: "recruitment.jpg",
+                attack: None,
+                health: None,
+            },
             Card::SteadyAim => CardData {
                 name: "Steady Aim",
                 energy_cost: 2,
@@ -459,6 +771,9 @@ impl Card {
                         ) else {
                             return false;
                         };
+                        if entity.entity.has_ability(AbilityFlag::SpellImmune) {
+                            return false;
+                        }
                         entity.state = EntityState::Dead;
                         true
                     })
@@ -468,7 +783,10 @@ impl Card {
                                 &play_args.dynamic_game_state.entities,
                                 Some(play_args.target.id),
                             )
-                            .is_some_and(|e| e.entity.tag == EntityTag::Tower)
+                            .is_some_and(|e| {
+                                e.entity.tag == EntityTag::Tower
+                                    && !e.entity.has_ability(AbilityFlag::SpellImmune)
+                            })
                         },
                     ),
                 ),
@@ -482,15 +800,16 @@ impl Card {
                 energy_cost: 10,
                 play_fn: PlayFn::WorldPos(SpecificPlayFn::new(
                     |play_args: PlayArgs<WorldPosTarget>| {
-                        for entity_instance in
-                            play_args.dynamic_game_state.entities.iter_mut().filter(
-                                |entity_instance| {
-                                    matches!(
-                                        entity_instance.entity.tag,
-                                        EntityTag::Unit | EntityTag::FlyingUnit
-                                    )
-                                },
-                            )
+                        for entity_instance in play_args
+                            .dynamic_game_state
+                            .entities
+                            .iter_mut()
+                            .filter(|entity_instance| {
+                                matches!(
+                                    entity_instance.entity.tag,
+                                    EntityTag::Unit | EntityTag::FlyingUnit
+                                ) && !entity_instance.entity.has_ability(AbilityFlag::SpellImmune)
+                            })
                         {
                             let sec = 60.0;
                             entity_instance.entity.seconds_left_to_live =
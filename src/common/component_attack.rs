@@ -1,17 +1,19 @@
 use crate::{
     buff::{apply_arithmetic_buffs, ArithmeticBuff},
-    component_health::Health,
-    component_movement::Movement,
-    config::PROJECTILE_RADIUS,
+    component_projectile::{Projectile, ProjectileSpec},
+    component_status_effects::StatusEffect,
+    component_trigger::{Trigger, TriggerEvent},
     entities::Entities,
-    entity::{Entity, EntityState, EntityTag},
+    entity::{EntityInstance, EntityTag},
     enum_flags::{flags, EnumFlags},
     find_target::{find_entities_in_range, find_target_id_for_attack},
     ids::{EntityId, PlayerId},
+    server_player::{are_allies, ServerPlayer},
     update_args::UpdateArgs,
 };
 use macroquad::math::Vec2;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AttackRange {
@@ -67,26 +69,55 @@ pub enum TargetPool {
 }
 
 impl TargetPool {
-    pub fn in_pool(&self, player_id_a: PlayerId, player_id_b: PlayerId) -> bool {
+    pub fn in_pool(
+        &self,
+        players: &HashMap<PlayerId, ServerPlayer>,
+        player_id_a: PlayerId,
+        player_id_b: PlayerId,
+    ) -> bool {
         match self {
-            TargetPool::Allies => player_id_a == player_id_b,
-            TargetPool::Enemies => player_id_a != player_id_b,
+            TargetPool::Allies => are_allies(players, player_id_a, player_id_b),
+            TargetPool::Enemies => !are_allies(players, player_id_a, player_id_b),
             TargetPool::All => true,
         }
     }
 }
 
+/// Which of the valid targets in range an attack picks
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum TargetPriority {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestHealth,
+    /// Highest damage per second
+    HighestDamage,
+    /// Closest to the end of its path
+    FurthestAlongPath,
+    /// Towers and bases before units
+    BuildingsFirst,
+    /// Stays on the previous target while it is valid, then picks the nearest
+    KeepCurrentTarget,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attack {
     pub damage: f32,
     pub attack_interval: AttackInterval,
     pub range: AttackRange,
     pub variant: AttackVariant,
+    pub effect: AttackEffect,
+    pub damage_type: DamageType,
+    pub damage_multipliers: Vec<(EntityTag, f32)>,
     pub target_pool: TargetPool,
     pub can_target: EnumFlags<EntityTag>,
+    pub target_priority: TargetPriority,
+    pub current_target_id: Option<EntityId>,
     pub cooldown_timer: f32,
+    /// Hits every valid target within this range of the attacker instead of a single target
     pub multi_attack_damage_range: Option<AttackRange>,
-    pub self_destruct: bool,
+    pub projectile: ProjectileSpec,
+    pub status_effects: Vec<StatusEffect>,
     pub damage_buffs: Vec<ArithmeticBuff>,
     pub attack_speed_buffs: Vec<ArithmeticBuff>,
     pub range_buffs: Vec<ArithmeticBuff>,
@@ -99,11 +130,17 @@ impl Default for Attack {
             attack_interval: AttackInterval::Default,
             range: AttackRange::Melee,
             variant: AttackVariant::MeleeAttack,
+            effect: AttackEffect::Damage,
+            damage_type: DamageType::Physical,
+            damage_multipliers: Vec::new(),
             target_pool: TargetPool::Enemies,
             can_target: flags![EntityTag::Base, EntityTag::Tower, EntityTag::Unit],
+            target_priority: TargetPriority::Nearest,
+            current_target_id: None,
             cooldown_timer: 0.0,
-            self_destruct: false,
+            status_effects: Vec::new(),
             multi_attack_damage_range: None,
+            projectile: ProjectileSpec::default(),
             damage_buffs: Vec::new(),
             attack_speed_buffs: Vec::new(),
             range_buffs: Vec::new(),
@@ -131,6 +168,14 @@ impl Attack {
             ..Attack::default()
         }
     }
+    pub fn default_heal() -> Self {
+        Self {
+            effect: AttackEffect::Heal,
+            target_pool: TargetPool::Allies,
+            can_target: flags![EntityTag::Unit, EntityTag::FlyingUnit],
+            ..Attack::default_ranged()
+        }
+    }
     pub fn default_ranged_tower() -> Self {
         Self {
             can_target: flags![EntityTag::Unit, EntityTag::FlyingUnit],
@@ -143,6 +188,14 @@ impl Attack {
     pub fn get_damage(&self) -> f32 {
         apply_arithmetic_buffs(self.damage, &self.damage_buffs)
     }
+    pub fn get_damage_against(&self, target_tag: &EntityTag) -> f32 {
+        self.damage_multipliers
+            .iter()
+            .filter(|(tag, _)| tag == target_tag)
+            .fold(self.get_damage(), |damage, (_, multiplier)| {
+                damage * multiplier
+            })
+    }
     pub fn get_attack_interval(&self) -> f32 {
         let attack_speed = self.attack_interval.as_f32().recip();
         apply_arithmetic_buffs(attack_speed, &self.attack_speed_buffs).recip()
@@ -150,6 +203,9 @@ impl Attack {
     pub fn get_range(&self, radius: f32) -> f32 {
         apply_arithmetic_buffs(self.range.to_f32(radius), &self.range_buffs)
     }
+    pub fn get_damage_per_second(&self) -> f32 {
+        self.get_damage() / self.get_attack_interval()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -158,81 +214,122 @@ pub enum AttackVariant {
     MeleeAttack,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DamageType {
+    /// Reduced by armor
+    Physical,
+    /// Reduced by half of armor
+    Piercing,
+    /// Ignores armor
+    Siege,
+    /// Reduced by magic resistance instead of armor
+    Magic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AttackEffect {
+    Damage,
+    /// Restores health instead of dealing damage, preferring the most damaged target
+    Heal,
+}
+
 impl Attack {
     pub fn update(update_args: &mut UpdateArgs) {
+        let source_id = update_args
+            .entity_instance
+            .entity
+            .projectile_source_id
+            .unwrap_or(update_args.entity_instance.id);
+        let mut has_attacked = false;
         for attack in &mut update_args.entity_instance.entity.attacks {
             let Some(target_id) = find_target_id_for_attack(
                 update_args.entity_instance.id,
                 update_args.entity_instance.entity.tag.clone(),
                 update_args.entity_instance.pos,
                 update_args.entity_instance.owner,
-                update_args.entity_instance.entity.spy.as_ref(),
+                update_args.entity_instance.entity.stealth.as_ref(),
                 attack.get_range(update_args.entity_instance.entity.radius),
                 attack,
+                update_args.static_game_state,
+                &update_args.dynamic_game_state.players,
                 &mut update_args.dynamic_game_state.entities,
             ) else {
+                attack.current_target_id = None;
                 continue;
             };
+            attack.current_target_id = Some(target_id);
             if attack.cooldown_timer <= 0.0 {
                 attack.cooldown_timer = attack.get_attack_interval();
                 attack.exec(
                     target_id,
+                    source_id,
                     update_args.entity_instance.pos,
+                    update_args.entity_instance.entity.radius,
                     update_args.entity_instance.owner,
+                    &update_args.dynamic_game_state.players,
                     &mut update_args.dynamic_game_state.entities,
                 );
-                if attack.self_destruct {
-                    update_args.entity_instance.state = EntityState::Dead;
-                };
+                has_attacked = true;
             } else {
                 attack.cooldown_timer -= update_args.dt;
             }
         }
+        if has_attacked {
+            if let Some(stealth) = &mut update_args.entity_instance.entity.stealth {
+                stealth.on_attack();
+            }
+            Trigger::fire(TriggerEvent::OnAttack, update_args);
+        }
     }
     fn exec(
         &mut self,
         target_id: EntityId,
+        source_id: EntityId,
         source_pos: Vec2,
+        source_radius: f32,
         source_owner: PlayerId,
+        players: &HashMap<PlayerId, ServerPlayer>,
         entities: &mut Entities,
     ) {
         let target_ids = match self.multi_attack_damage_range.as_ref() {
             None => vec![target_id],
-            Some(range) => find_entities_in_range(
-                source_pos,
-                range.to_f32(0.0), // TODO: fix this
-                self.can_target.clone(),
-                entities,
-                |_| true,
-            )
-            .iter()
-            .map(|e| e.id)
-            .collect(),
+            Some(range) => {
+                let target_pool = self.target_pool.clone();
+                find_entities_in_range(
+                    source_pos,
+                    range.to_f32(source_radius),
+                    self.can_target.clone(),
+                    entities,
+                    move |other| target_pool.in_pool(players, source_owner, other.owner),
+                )
+                .iter()
+                .map(|e| e.id)
+                .collect()
+            }
         };
         for target_id in target_ids {
             match self.variant {
-                AttackVariant::RangedAttack => {
-                    let bullet = Entity {
-                        tag: EntityTag::Bullet,
-                        radius: PROJECTILE_RADIUS,
-                        hitbox_radius: PROJECTILE_RADIUS,
-                        health: Health::new(1.0),
-                        movement: Some(Movement::new_projectile(target_id)),
-                        seconds_left_to_live: Some(3.0),
-                        attacks: vec![Attack {
-                            damage: self.get_damage(),
-                            can_target: self.can_target.clone(),
-                            self_destruct: true,
-                            ..Attack::default()
-                        }],
-                        ..Entity::default()
-                    }
-                    .instantiate(source_owner, source_pos);
-                    entities.spawn(bullet);
-                }
+                AttackVariant::RangedAttack => Projectile::fire(
+                    Attack {
+                        damage: self.get_damage(),
+                        effect: self.effect.clone(),
+                        damage_type: self.damage_type.clone(),
+                        damage_multipliers: self.damage_multipliers.clone(),
+                        target_pool: self.target_pool.clone(),
+                        status_effects: self.status_effects.clone(),
+                        can_target: self.can_target.clone(),
+                        projectile: self.projectile.clone(),
+                        ..Attack::default()
+                    },
+                    target_id,
+                    source_id,
+                    source_pos,
+                    source_owner,
+                    entities,
+                ),
                 AttackVariant::MeleeAttack => {
                     if let Some(target) = entities.iter_mut().find(|e| e.id == target_id) {
-                        target.entity.health.deal_damage(self.get_damage());
+                        self.apply(target, source_id);
                     } else {
                         debug_assert!(false);
                     }
@@ -240,4 +337,20 @@ impl Attack {
             }
         }
     }
+
+    pub fn apply(&self, target: &mut EntityInstance, source_id: EntityId) {
+        match self.effect {
+            AttackEffect::Damage => {
+                target.entity.health.deal_typed_damage(
+                    self.get_damage_against(&target.entity.tag),
+                    &self.damage_type,
+                );
+                target.entity.health.last_damaged_by = Some(source_id);
+            }
+            AttackEffect::Heal => target.entity.health.heal(self.get_damage()),
+        }
+        for status_effect in &self.status_effects {
+            target.entity.status_effects.apply(status_effect.clone());
+        }
+    }
 }
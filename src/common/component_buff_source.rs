@@ -1,6 +1,8 @@
 use crate::{
     buff::{buff_add_to_components, buff_add_to_entity, Buff},
-    entity::EntityState,
+    component_status_effects::StatusEffect,
+    config::REVEAL_SECONDS,
+    entity::{AbilityFlag, EntityState, EntityTag},
     entity_filter::{EntityFilter, Tof32},
     update_args::UpdateArgs,
 };
@@ -10,6 +12,7 @@ use serde::{Deserialize, Serialize};
 pub enum BuffRange {
     #[default]
     Default,
+    Custom(f32),
 }
 
 impl Tof32 for BuffRange {
@@ -17,6 +20,7 @@ impl Tof32 for BuffRange {
         let default = 256.0;
         match self {
             Self::Default => default,
+            Self::Custom(range) => *range,
         }
     }
 }
@@ -28,6 +32,23 @@ pub struct BuffSource {
     pub target_filter: BuffTargetFilter,
 }
 
+/// Continuous effect applied every frame to entities in range, scaled by dt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuraEffect {
+    HealthRegeneration(f32),
+    ContinuousDamage(f32),
+    /// Reapplied every frame, so it lingers for its duration after leaving the range
+    StatusEffect(StatusEffect),
+    /// Detects stealthed entities, keeping them targetable shortly after leaving the range
+    Reveal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuraSource {
+    pub effect: AuraEffect,
+    pub target_filter: EntityFilter<BuffRange>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum BuffCondition {
     #[default]
@@ -42,6 +63,45 @@ pub enum BuffTargetFilter {
     OnSpawn(EntityFilter<BuffRange>),
 }
 
+impl AuraSource {
+    pub fn update(update_args: &mut UpdateArgs) {
+        // Zones are spells, so they don't affect spell immune entities
+        let from_zone = update_args.entity_instance.entity.tag == EntityTag::Zone;
+        for aura_source in update_args.entity_instance.entity.aura_sources.iter() {
+            for entity_instance in update_args
+                .dynamic_game_state
+                .entities
+                .iter_mut()
+                .filter(aura_source.target_filter.to_fn_mut(
+                    update_args.entity_instance,
+                    &update_args.dynamic_game_state.players,
+                ))
+                .filter(|entity_instance| {
+                    !from_zone || !entity_instance.entity.has_ability(AbilityFlag::SpellImmune)
+                })
+            {
+                let entity = &mut entity_instance.entity;
+                match &aura_source.effect {
+                    AuraEffect::HealthRegeneration(per_second) => {
+                        entity.health.heal(per_second * update_args.dt)
+                    }
+                    AuraEffect::ContinuousDamage(per_second) => entity
+                        .health
+                        .deal_continuous_damage(per_second * update_args.dt),
+                    AuraEffect::StatusEffect(status_effect) => {
+                        entity.status_effects.apply(status_effect.clone())
+                    }
+                    AuraEffect::Reveal => {
+                        if let Some(stealth) = &mut entity.stealth {
+                            stealth.reveal(REVEAL_SECONDS);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl BuffSource {
     pub fn update(update_args: &mut UpdateArgs) {
         for buff_source in update_args.entity_instance.entity.buff_sources.iter() {
@@ -51,7 +111,10 @@ impl BuffSource {
                     .dynamic_game_state
                     .entities
                     .iter()
-                    .filter(entity_filter.to_fn(update_args.entity_instance))
+                    .filter(entity_filter.to_fn(
+                        update_args.entity_instance,
+                        &update_args.dynamic_game_state.players,
+                    ))
                     .count(),
             };
             for _ in 0..apply_n {
@@ -63,11 +126,13 @@ impl BuffSource {
                         &mut update_args.entity_instance.entity.health,
                     ),
                     BuffTargetFilter::EntityFilter(ref entity_filter) => {
-                        for entity_instance in update_args
-                            .dynamic_game_state
-                            .entities
-                            .iter_mut()
-                            .filter(entity_filter.to_fn_mut(update_args.entity_instance))
+                        for entity_instance in
+                            update_args.dynamic_game_state.entities.iter_mut().filter(
+                                entity_filter.to_fn_mut(
+                                    update_args.entity_instance,
+                                    &update_args.dynamic_game_state.players,
+                                ),
+                            )
                         {
                             buff_add_to_entity(
                                 buff_source.buff.clone(),
@@ -83,7 +148,10 @@ impl BuffSource {
                             .filter(|entity_instance| {
                                 entity_instance.state == EntityState::SpawnFrame
                             })
-                            .filter(entity_filter.to_fn_mut(update_args.entity_instance))
+                            .filter(entity_filter.to_fn_mut(
+                                update_args.entity_instance,
+                                &update_args.dynamic_game_state.players,
+                            ))
                         {
                             buff_add_to_entity(
                                 buff_source.buff.clone(),
@@ -0,0 +1,56 @@
+use crate::{entity::Entity, update_args::UpdateArgs};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BuildTime {
+    Regular,
+    Slow,
+}
+
+impl BuildTime {
+    pub fn to_f32(&self) -> f32 {
+        let regular = 5.0;
+        match self {
+            BuildTime::Regular => regular,
+            BuildTime::Slow => regular * 3.0,
+        }
+    }
+}
+
+/// A building under construction does nothing but gain health until progress reaches 1.0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Construction {
+    pub build_time: BuildTime,
+    pub progress: f32,
+}
+
+impl Construction {
+    const STARTING_HEALTH_FRACTION: f32 = 0.1;
+
+    pub fn start(entity: &mut Entity, build_time: BuildTime) {
+        entity.health.health = entity.health.max_health * Self::STARTING_HEALTH_FRACTION;
+        entity.construction = Some(Construction {
+            build_time,
+            progress: 0.0,
+        });
+    }
+}
+
+impl Construction {
+    pub fn update(update_args: &mut UpdateArgs) {
+        let entity = &mut update_args.entity_instance.entity;
+        let Some(construction) = &mut entity.construction else {
+            return;
+        };
+        let progress = update_args.dt / construction.build_time.to_f32();
+        construction.progress += progress;
+        // Damage taken while under construction is not restored
+        let health = &mut entity.health;
+        health.health = (health.health
+            + health.max_health * (1.0 - Self::STARTING_HEALTH_FRACTION) * progress)
+            .min(health.max_health);
+        if construction.progress >= 1.0 {
+            entity.construction = None;
+        }
+    }
+}
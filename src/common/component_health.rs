@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{buff::ExtraHealthBuff, entity::EntityState, update_args::UpdateArgs};
+use crate::{
+    buff::ExtraHealthBuff, component_attack::DamageType, entity::EntityState, ids::EntityId,
+    update_args::UpdateArgs,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Health {
@@ -8,6 +11,14 @@ pub struct Health {
     pub health: f32,
     pub extra_health_buffs: Vec<ExtraHealthBuff>,
     pub damage_animation: f32,
+    /// Fraction of physical damage blocked
+    pub armor: f32,
+    /// Fraction of magic damage blocked
+    pub magic_resistance: f32,
+    /// Set by discrete damage, cleared when OnDamaged triggers have been fired
+    pub was_hit: bool,
+    /// Credited with the kill if this entity dies from damage
+    pub last_damaged_by: Option<EntityId>,
 }
 impl Health {
     pub fn new(max_health: f32) -> Self {
@@ -18,7 +29,39 @@ impl Health {
         }
     }
 
+    pub fn with_armor(mut self, armor: f32) -> Self {
+        self.armor = armor;
+        self
+    }
+
+    pub fn with_magic_resistance(mut self, magic_resistance: f32) -> Self {
+        self.magic_resistance = magic_resistance;
+        self
+    }
+
+    pub fn get_damage_taken(&self, damage: f32, damage_type: &DamageType) -> f32 {
+        let reduction = match damage_type {
+            DamageType::Physical => self.armor,
+            DamageType::Piercing => self.armor / 2.0,
+            DamageType::Siege => 0.0,
+            DamageType::Magic => self.magic_resistance,
+        };
+        damage * (1.0 - reduction.clamp(0.0, 1.0))
+    }
+
+    pub fn deal_typed_damage(&mut self, damage: f32, damage_type: &DamageType) {
+        self.deal_damage(self.get_damage_taken(damage, damage_type));
+    }
+
     pub fn deal_damage(&mut self, damage: f32) {
+        self.deal_continuous_damage(damage);
+        self.damage_animation = 0.1;
+        self.was_hit = true;
+    }
+
+    /// For damage dealt every frame. Does not restart the damage animation, which would otherwise
+    /// keep the entity from dying
+    pub fn deal_continuous_damage(&mut self, damage: f32) {
         let mut damage = damage;
         for buff in self.extra_health_buffs.iter_mut() {
             if buff.health <= 0.0 {
@@ -29,7 +72,6 @@ impl Health {
             buff.health -= damage_to_take;
         }
         self.health -= damage;
-        self.damage_animation = 0.1;
     }
 
     pub fn heal(&mut self, damage: f32) {
@@ -47,6 +89,15 @@ impl Health {
         }
     }
 
+    pub fn get_missing_health(&self) -> f32 {
+        (self.max_health - self.health)
+            + self
+                .extra_health_buffs
+                .iter()
+                .map(|buff| buff.max_health - buff.health)
+                .sum::<f32>()
+    }
+
     pub fn get_health(&self) -> f32 {
         self.health
             + self
@@ -1,12 +1,18 @@
 use crate::{
     buff::{apply_arithmetic_buffs, ArithmeticBuff},
+    component_attack::TargetPool,
     config::{CLOSE_ENOUGH_TO_TARGET, DEFAULT_UNIT_DETECTION_RADIUS},
-    entity::{AbilityFlag, Entity},
+    entity::{AbilityFlag, Entity, EntityTag},
     find_target::find_targets_for_attack,
-    game_state::StaticGameState,
-    ids::{EntityId, PathId},
+    game_state::{DynamicGameState, StaticGameState},
+    ids::{EntityId, PathId, PlayerId},
+    path_graph::{
+        choose_branch, distance_along_graph_to, distance_to_graph_end, path_end_idx,
+        paths_are_connected, BranchPolicy,
+    },
     play_target::UnitSpawnpointTarget,
     serde_defs::Vec2Def,
+    server_player::are_allies,
     update_args::UpdateArgs,
     world::{
         find_entity, get_path_pos, next_path_idx, world_get_furthest_planned_or_existing_building,
@@ -22,6 +28,7 @@ pub struct Movement {
     pub path_target_setter: Option<PathTargetSetter>,
     pub detection_based_target_setter: Option<DetectionBasedTargetSetter>,
     pub entity_target_setter: Option<EntityTargetSetter>,
+    pub flight_target_setter: Option<FlightTargetSetter>,
 }
 
 impl Movement {
@@ -39,13 +46,21 @@ impl Movement {
                 detection_range: DEFAULT_UNIT_DETECTION_RADIUS,
             }),
             entity_target_setter: None,
+            flight_target_setter: None,
         }
     }
-    pub fn new_projectile(target_entity_id: EntityId) -> Self {
+    /// Flies off the paths, straight towards the enemy base
+    pub fn new_flying(speed: MovementSpeed) -> Self {
+        Self {
+            flight_target_setter: Some(FlightTargetSetter),
+            ..Movement::new(speed)
+        }
+    }
+    pub fn new_projectile(target_entity_id: EntityId, speed: MovementSpeed) -> Self {
         Self {
             movement_towards_target: MovementTowardsTarget {
                 target_pos: None,
-                speed: MovementSpeed::Projectile,
+                speed,
                 speed_buffs: vec![],
                 velocity: Vec2::ZERO,
                 keep_moving_on_loss_of_target: true,
@@ -55,6 +70,24 @@ impl Movement {
             entity_target_setter: Some(EntityTargetSetter {
                 target_entity_id: Some(target_entity_id),
             }),
+            flight_target_setter: None,
+        }
+    }
+
+    /// Flies straight to a fixed position
+    pub fn new_ballistic_projectile(target_pos: Vec2, speed: MovementSpeed) -> Self {
+        Self {
+            movement_towards_target: MovementTowardsTarget {
+                target_pos: Some(target_pos),
+                speed,
+                speed_buffs: vec![],
+                velocity: Vec2::ZERO,
+                keep_moving_on_loss_of_target: false,
+            },
+            path_target_setter: None,
+            detection_based_target_setter: None,
+            entity_target_setter: None,
+            flight_target_setter: None,
         }
     }
 }
@@ -75,6 +108,9 @@ pub struct EntityTargetSetter {
     pub target_entity_id: Option<EntityId>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlightTargetSetter;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionBasedTargetSetter {
     pub detection_range: f32,
@@ -98,26 +134,75 @@ pub struct PathState {
     pub path_id: PathId,
     pub target_path_idx: usize,
     pub direction: Direction,
+    pub branch_policy: BranchPolicy,
 }
 
-pub fn get_path_id(entity: &Entity) -> Option<PathId> {
+pub fn get_path_state(entity: &Entity) -> Option<&PathState> {
     entity
         .movement
         .as_ref()
         .and_then(|movement| movement.path_target_setter.as_ref())
         .and_then(|path_target_setter| path_target_setter.path_state.as_ref())
-        .map(|path_state| path_state.path_id)
+}
+
+pub fn get_path_id(entity: &Entity) -> Option<PathId> {
+    get_path_state(entity).map(|path_state| path_state.path_id)
 }
 
 impl PathState {
-    pub fn incr(&mut self, static_game_state: &StaticGameState) {
-        self.target_path_idx = next_path_idx(self.target_path_idx, self.direction.clone());
+    /// Moves on to the next node, switching path by the branch policy at a junction
+    pub fn incr(
+        &mut self,
+        static_game_state: &StaticGameState,
+        dynamic_game_state: &DynamicGameState,
+        owner: PlayerId,
+    ) {
+        let path = static_game_state.paths.get(&self.path_id).unwrap();
+        let is_at_path_end = match self.direction {
+            Direction::Positive => self.target_path_idx >= path.len() - 1,
+            Direction::Negative => self.target_path_idx == 0,
+        };
+        if !is_at_path_end {
+            self.target_path_idx = next_path_idx(self.target_path_idx, self.direction.clone());
+            self.target_path_idx = usize::min(self.target_path_idx, path.len() - 1);
+            return;
+        }
+        let Some((branch_id, branch_direction)) = choose_branch(
+            static_game_state,
+            dynamic_game_state,
+            owner,
+            self.path_id,
+            &self.direction,
+            &self.branch_policy,
+        ) else {
+            return;
+        };
+        let branch = static_game_state.paths.get(&branch_id).unwrap();
+        // The first node of the branch is the junction we are standing on
+        self.path_id = branch_id;
+        self.direction = branch_direction;
         self.target_path_idx = usize::min(
-            self.target_path_idx,
-            static_game_state.paths.get(&self.path_id).unwrap().len() - 1,
+            next_path_idx(
+                path_end_idx(branch, &self.direction.flipped()),
+                self.direction.clone(),
+            ),
+            branch.len() - 1,
         );
     }
 
+    /// Distance left to walk from `pos` to the end of the graph
+    pub fn get_remaining_distance(&self, static_game_state: &StaticGameState, pos: Vec2) -> f32 {
+        let path = static_game_state.paths.get(&self.path_id).unwrap();
+        let target_path_idx = self.target_path_idx.min(path.len() - 1);
+        (get_path_pos(static_game_state, self.path_id, target_path_idx) - pos).length()
+            + distance_to_graph_end(
+                static_game_state,
+                self.path_id,
+                target_path_idx,
+                &self.direction,
+            )
+    }
+
     pub fn set_direction(&mut self, direction: Direction) {
         if direction == self.direction {
             return;
@@ -133,6 +218,7 @@ impl From<UnitSpawnpointTarget> for PathState {
             path_id: target.path_id,
             target_path_idx: next_path_idx(target.path_idx, target.direction.clone()),
             direction: target.direction,
+            branch_policy: target.branch_policy,
         }
     }
 }
@@ -171,7 +257,16 @@ impl MovementTowardsTarget {
 
 impl Movement {
     pub fn update(update_args: &mut UpdateArgs) {
+        if update_args
+            .entity_instance
+            .entity
+            .status_effects
+            .is_rooted()
+        {
+            return;
+        }
         PathTargetSetter::update(update_args);
+        FlightTargetSetter::update(update_args);
         DetectionBasedTargetSetter::update(update_args);
         EntityTargetSetter::update(update_args);
         MovementTowardsTarget::update(update_args);
@@ -180,6 +275,12 @@ impl Movement {
 
 impl MovementTowardsTarget {
     pub fn update(update_args: &mut UpdateArgs) {
+        let speed_multiplier = update_args
+            .entity_instance
+            .entity
+            .status_effects
+            .get_speed_multiplier();
+        let separation = get_separation(update_args);
         let Some(movement) = &mut update_args.entity_instance.entity.movement else {
             return;
         };
@@ -190,8 +291,16 @@ impl MovementTowardsTarget {
                 update_args.entity_instance.pos = target_pos;
                 movement_towards_target.target_pos = None;
             } else {
+                let direction = diff.normalize_or_zero();
+                let mut steering = (direction + separation.push).clamp_length_max(1.0);
+                if separation
+                    .blocker_direction
+                    .is_some_and(|blocker_direction| blocker_direction.dot(direction) > 0.0)
+                {
+                    steering -= direction * steering.dot(direction).max(0.0);
+                }
                 movement_towards_target.velocity =
-                    diff.normalize_or_zero() * movement_towards_target.get_speed();
+                    steering * movement_towards_target.get_speed() * speed_multiplier;
                 update_args.entity_instance.pos +=
                     movement_towards_target.velocity * update_args.dt;
             }
@@ -201,6 +310,58 @@ impl MovementTowardsTarget {
     }
 }
 
+struct Separation {
+    push: Vec2,
+    blocker_direction: Option<Vec2>,
+}
+
+/// Ground units steer away from overlapping ground units, proportionally to the overlap, and stop
+/// advancing towards allies that block the lane. Flying units are unaffected
+fn get_separation(update_args: &UpdateArgs) -> Separation {
+    let entity_instance = &update_args.entity_instance;
+    let mut separation = Separation {
+        push: Vec2::ZERO,
+        blocker_direction: None,
+    };
+    if entity_instance.entity.tag != EntityTag::Unit {
+        return separation;
+    }
+    for other in update_args
+        .dynamic_game_state
+        .entities
+        .iter()
+        .filter(|other| other.entity.tag == EntityTag::Unit)
+    {
+        let diff = entity_instance.pos - other.pos;
+        let distance = diff.length();
+        // Sprites may overlap by half their size, so groups stay tight
+        let min_distance = (entity_instance.entity.radius + other.entity.radius) / 2.0;
+        if distance >= min_distance {
+            continue;
+        }
+        // Units spawned on the same spot are split by id, so they don't move as one
+        let away = match diff.try_normalize() {
+            Some(away) => away,
+            None if entity_instance.id.0 < other.id.0 => Vec2::Y,
+            None => -Vec2::Y,
+        };
+        let overlap = 1.0 - distance / min_distance;
+        if are_allies(
+            &update_args.dynamic_game_state.players,
+            other.owner,
+            entity_instance.owner,
+        ) && other.entity.has_ability(AbilityFlag::BlocksAllies)
+        {
+            separation.push += away * overlap;
+            separation.blocker_direction = Some(-away);
+        } else {
+            // The tangential part makes units walk around each other instead of pushing head on
+            separation.push += (away + away.perp()) * overlap;
+        }
+    }
+    separation
+}
+
 impl PathTargetSetter {
     pub fn update(update_args: &mut UpdateArgs) {
         let Some(movement) = update_args.entity_instance.entity.movement.as_mut() else {
@@ -219,7 +380,7 @@ impl PathTargetSetter {
             .ability_flags
             .is_set(&AbilityFlag::Protector)
         {
-            if let Some((_, _, building_location_closest_path_idx)) =
+            if let Some((_, _, building_path_id, building_path_idx)) =
                 world_get_furthest_planned_or_existing_building(
                     path_state.path_id,
                     update_args.entity_instance.owner,
@@ -229,24 +390,47 @@ impl PathTargetSetter {
                     update_args.dynamic_game_state,
                 )
             {
-                if (get_path_pos(
+                let building_node_pos = get_path_pos(
                     update_args.static_game_state,
-                    path_state.path_id,
-                    building_location_closest_path_idx,
-                ) - update_args.entity_instance.pos)
-                    .length()
+                    building_path_id,
+                    building_path_idx,
+                );
+                if (building_node_pos - update_args.entity_instance.pos).length()
                     < CLOSE_ENOUGH_TO_TARGET
                 {
                     movement.movement_towards_target.target_pos = None;
                     return;
                 }
-                if building_location_closest_path_idx != path_state.target_path_idx {
-                    path_state.set_direction(
-                        match building_location_closest_path_idx > path_state.target_path_idx {
-                            true => Direction::Positive,
-                            false => Direction::Negative,
-                        },
-                    );
+                if building_path_id == path_state.path_id {
+                    if building_path_idx != path_state.target_path_idx {
+                        path_state.set_direction(
+                            match building_path_idx > path_state.target_path_idx {
+                                true => Direction::Positive,
+                                false => Direction::Negative,
+                            },
+                        );
+                    }
+                } else {
+                    // The building is past a junction, so head for it along the graph
+                    path_state.branch_policy = BranchPolicy::Towards(building_node_pos);
+                    if let Some(direction) = [Direction::Positive, Direction::Negative]
+                        .into_iter()
+                        .filter_map(|direction| {
+                            distance_along_graph_to(
+                                update_args.static_game_state,
+                                path_state.path_id,
+                                path_state.target_path_idx,
+                                &direction,
+                                building_node_pos,
+                                CLOSE_ENOUGH_TO_TARGET,
+                            )
+                            .map(|distance| (direction, distance))
+                        })
+                        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .map(|(direction, _)| direction)
+                    {
+                        path_state.set_direction(direction);
+                    }
                 }
             } else {
                 path_state.set_direction(
@@ -269,7 +453,11 @@ impl PathTargetSetter {
         let pos_diff = target_pos - update_args.entity_instance.pos;
 
         if pos_diff.length() < CLOSE_ENOUGH_TO_TARGET {
-            path_state.incr(update_args.static_game_state);
+            path_state.incr(
+                update_args.static_game_state,
+                update_args.dynamic_game_state,
+                update_args.entity_instance.owner,
+            );
             target_pos = get_path_pos(
                 update_args.static_game_state,
                 path_state.path_id,
@@ -281,6 +469,30 @@ impl PathTargetSetter {
     }
 }
 
+impl FlightTargetSetter {
+    pub fn update(update_args: &mut UpdateArgs) {
+        let Some(movement) = update_args.entity_instance.entity.movement.as_mut() else {
+            return;
+        };
+        if movement.flight_target_setter.is_none() {
+            return;
+        }
+        let pos = update_args.entity_instance.pos;
+        let owner = update_args.entity_instance.owner;
+        let players = &update_args.dynamic_game_state.players;
+        movement.movement_towards_target.target_pos = update_args
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|other| {
+                other.entity.tag == EntityTag::Base
+                    && TargetPool::Enemies.in_pool(players, owner, other.owner)
+            })
+            .map(|other| other.pos)
+            .min_by(|a, b| a.distance(pos).total_cmp(&b.distance(pos)));
+    }
+}
+
 impl DetectionBasedTargetSetter {
     pub fn update(update_args: &mut UpdateArgs) {
         let entity_path_id = get_path_id(&update_args.entity_instance.entity);
@@ -319,16 +531,18 @@ impl DetectionBasedTargetSetter {
                 update_args.entity_instance.entity.tag.clone(),
                 update_args.entity_instance.pos,
                 update_args.entity_instance.owner,
-                update_args.entity_instance.entity.spy.as_ref(),
+                update_args.entity_instance.entity.stealth.as_ref(),
                 detection_range,
                 attack,
+                &update_args.dynamic_game_state.players,
                 &mut update_args.dynamic_game_state.entities,
             )
             .first()
             {
                 if entity_path_id.is_some_and(|id| {
-                    get_path_id(&target_entity_instance_to_attack.entity)
-                        .is_some_and(|other_id| id != other_id)
+                    get_path_id(&target_entity_instance_to_attack.entity).is_some_and(|other_id| {
+                        !paths_are_connected(update_args.static_game_state, id, other_id)
+                    })
                 }) {
                     continue;
                 }
@@ -0,0 +1,199 @@
+use crate::{
+    component_attack::{Attack, AttackRange},
+    component_health::Health,
+    component_movement::{Movement, MovementSpeed},
+    config::PROJECTILE_RADIUS,
+    entities::Entities,
+    entity::{Entity, EntityInstance, EntityState, EntityTag},
+    find_target::{find_entities_in_range, find_targets_for_attack},
+    ids::{EntityId, PlayerId},
+    serde_defs::Vec2Def,
+    update_args::UpdateArgs,
+    world::find_entity,
+};
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Trajectory {
+    /// Follows the target and hits whatever it touches first
+    Homing,
+    /// Lands where the target was when fired, and misses if the target has moved
+    Ballistic,
+}
+
+/// How the projectiles of a ranged attack fly and hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileSpec {
+    pub trajectory: Trajectory,
+    pub speed: MovementSpeed,
+    /// Every valid target within this range of an impact is hit
+    pub splash_range: Option<AttackRange>,
+    /// Extra targets a homing projectile passes through before it is spent
+    pub pierce: usize,
+}
+
+impl Default for ProjectileSpec {
+    fn default() -> Self {
+        Self {
+            trajectory: Trajectory::Homing,
+            speed: MovementSpeed::Projectile,
+            splash_range: None,
+            pierce: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProjectileTarget {
+    Entity(EntityId),
+    Position(#[serde(with = "Vec2Def")] Vec2),
+}
+
+/// A projectile in flight, carrying the attack it applies on impact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Projectile {
+    pub spec: ProjectileSpec,
+    pub payload: Attack,
+    pub target: ProjectileTarget,
+    pub hit_entity_ids: Vec<EntityId>,
+}
+
+impl Projectile {
+    pub fn fire(
+        payload: Attack,
+        target_id: EntityId,
+        source_id: EntityId,
+        source_pos: Vec2,
+        source_owner: PlayerId,
+        entities: &mut Entities,
+    ) {
+        let Some(target_pos) = find_entity(entities, Some(target_id)).map(|target| target.pos)
+        else {
+            debug_assert!(false);
+            return;
+        };
+        let spec = payload.projectile.clone();
+        let (movement, target) = match spec.trajectory {
+            Trajectory::Homing => (
+                Movement::new_projectile(target_id, spec.speed.clone()),
+                ProjectileTarget::Entity(target_id),
+            ),
+            Trajectory::Ballistic => (
+                Movement::new_ballistic_projectile(target_pos, spec.speed.clone()),
+                ProjectileTarget::Position(target_pos),
+            ),
+        };
+        let flight_time = source_pos.distance(target_pos) / spec.speed.to_f32();
+        let bullet = Entity {
+            tag: EntityTag::Bullet,
+            radius: PROJECTILE_RADIUS,
+            hitbox_radius: PROJECTILE_RADIUS,
+            health: Health::new(1.0),
+            movement: Some(movement),
+            seconds_left_to_live: Some(f32::max(3.0, flight_time + 1.0)),
+            projectile: Some(Projectile {
+                spec,
+                payload,
+                target,
+                hit_entity_ids: Vec::new(),
+            }),
+            projectile_source_id: Some(source_id),
+            ..Entity::default()
+        }
+        .instantiate(source_owner, source_pos);
+        entities.spawn(bullet);
+    }
+}
+
+impl Projectile {
+    pub fn update(update_args: &mut UpdateArgs) {
+        let entity_instance = &mut update_args.entity_instance;
+        let Some(projectile) = &entity_instance.entity.projectile else {
+            return;
+        };
+        let direct_hit_id = find_targets_for_attack(
+            entity_instance.id,
+            entity_instance.entity.tag.clone(),
+            entity_instance.pos,
+            entity_instance.owner,
+            None,
+            entity_instance.entity.hitbox_radius,
+            &projectile.payload,
+            &update_args.dynamic_game_state.players,
+            &mut update_args.dynamic_game_state.entities,
+        )
+        .into_iter()
+        .map(|target| target.id)
+        .find(|target_id| !projectile.hit_entity_ids.contains(target_id));
+
+        let is_spent = match projectile.target {
+            ProjectileTarget::Entity(_) => {
+                let Some(direct_hit_id) = direct_hit_id else {
+                    return;
+                };
+                Projectile::impact(update_args, Some(direct_hit_id));
+                update_args
+                    .entity_instance
+                    .entity
+                    .projectile
+                    .as_ref()
+                    .is_none_or(|projectile| {
+                        projectile.hit_entity_ids.len() > projectile.spec.pierce
+                    })
+            }
+            ProjectileTarget::Position(target_pos) => {
+                if entity_instance.pos != target_pos {
+                    return;
+                }
+                Projectile::impact(update_args, direct_hit_id);
+                true
+            }
+        };
+        if is_spent {
+            update_args.entity_instance.state = EntityState::Dead;
+        } else if let Some(movement) = &mut update_args.entity_instance.entity.movement {
+            // Keep flying in a straight line instead of following the target that was hit
+            movement.entity_target_setter = None;
+            movement.movement_towards_target.target_pos = None;
+        }
+    }
+
+    fn impact(update_args: &mut UpdateArgs, direct_hit_id: Option<EntityId>) {
+        let entity_instance = &mut update_args.entity_instance;
+        let source_id = entity_instance
+            .entity
+            .projectile_source_id
+            .unwrap_or(entity_instance.id);
+        let Some(projectile) = &mut entity_instance.entity.projectile else {
+            return;
+        };
+        if let Some(direct_hit_id) = direct_hit_id {
+            projectile.hit_entity_ids.push(direct_hit_id);
+        }
+        let payload = &projectile.payload;
+        let targets: Vec<&mut EntityInstance> = match &projectile.spec.splash_range {
+            Some(splash_range) => {
+                let owner = entity_instance.owner;
+                let target_pool = payload.target_pool.clone();
+                let players = &update_args.dynamic_game_state.players;
+                find_entities_in_range(
+                    entity_instance.pos,
+                    splash_range.to_f32(entity_instance.entity.radius),
+                    payload.can_target.clone(),
+                    &mut update_args.dynamic_game_state.entities,
+                    move |other| target_pool.in_pool(players, owner, other.owner),
+                )
+            }
+            None => update_args
+                .dynamic_game_state
+                .entities
+                .iter_mut()
+                .filter(|other| Some(other.id) == direct_hit_id)
+                .collect(),
+        };
+        for target in targets {
+            payload.apply(target, source_id);
+        }
+    }
+}
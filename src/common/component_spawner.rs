@@ -0,0 +1,90 @@
+use crate::{
+    entity_blueprint::EntityBlueprint,
+    ids::EntityId,
+    update_args::UpdateArgs,
+    world::{find_entity, world_get_closest_spawnpoint, world_place_path_entity},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spawner {
+    pub entity_blueprint: EntityBlueprint,
+    pub spawn_interval: f32,
+    /// Spawning stops while this many spawned units are alive
+    pub max_alive: Option<usize>,
+    /// Spawning stops while the spawner is below max health
+    pub pause_while_damaged: bool,
+    pub seconds_until_spawn: f32,
+    pub spawned_entity_ids: Vec<EntityId>,
+}
+
+impl Spawner {
+    pub fn new(entity_blueprint: EntityBlueprint, spawn_interval: f32) -> Self {
+        Self {
+            entity_blueprint,
+            spawn_interval,
+            max_alive: None,
+            pause_while_damaged: false,
+            seconds_until_spawn: spawn_interval,
+            spawned_entity_ids: Vec::new(),
+        }
+    }
+    pub fn with_max_alive(mut self, max_alive: usize) -> Self {
+        self.max_alive = Some(max_alive);
+        self
+    }
+    pub fn with_pause_while_damaged(mut self) -> Self {
+        self.pause_while_damaged = true;
+        self
+    }
+}
+
+impl Spawner {
+    pub fn update(update_args: &mut UpdateArgs) {
+        let entity = &mut update_args.entity_instance.entity;
+        let is_damaged = entity.health.health < entity.health.max_health;
+        let Some(spawner) = &mut entity.spawner else {
+            return;
+        };
+        spawner.spawned_entity_ids.retain(|id| {
+            find_entity(&update_args.dynamic_game_state.entities, Some(*id)).is_some()
+        });
+        if spawner.pause_while_damaged && is_damaged {
+            return;
+        }
+        if spawner
+            .max_alive
+            .is_some_and(|max_alive| spawner.spawned_entity_ids.len() >= max_alive)
+        {
+            spawner.seconds_until_spawn = spawner.spawn_interval;
+            return;
+        }
+        spawner.seconds_until_spawn -= update_args.dt;
+        if spawner.seconds_until_spawn > 0.0 {
+            return;
+        }
+        spawner.seconds_until_spawn = spawner.spawn_interval;
+
+        let owner = update_args.entity_instance.owner;
+        let Some(player) = update_args.dynamic_game_state.players.get(&owner) else {
+            return;
+        };
+        let direction = player.direction.clone();
+        let Some(spawnpoint) = world_get_closest_spawnpoint(
+            update_args.static_game_state,
+            update_args.entity_instance.pos,
+            direction,
+        ) else {
+            return;
+        };
+        if let Some(spawned_entity_id) = world_place_path_entity(
+            update_args.static_game_state,
+            update_args.dynamic_game_state,
+            spawnpoint,
+            spawner.entity_blueprint.create(),
+            owner,
+        ) {
+            spawner.spawned_entity_ids.push(spawned_entity_id);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{enum_flags::EnumFlags, update_args::UpdateArgs};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StatusEffectKind {
+    Stun,
+    Root,
+    Slow,
+    Poison,
+    Burn,
+}
+
+impl From<StatusEffectKind> for usize {
+    fn from(val: StatusEffectKind) -> Self {
+        val as usize
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StatusEffectStacking {
+    /// Reapplying replaces the magnitude and keeps the longest duration
+    Refresh,
+    /// Reapplying adds another instance, up to max_stacks. Beyond that the oldest instance is
+    /// refreshed instead
+    Stack { max_stacks: usize },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    /// Movement speed multiplier for slow, damage per second for poison and burn
+    pub magnitude: f32,
+    pub seconds_left: f32,
+    pub stacking: StatusEffectStacking,
+}
+
+impl StatusEffect {
+    pub fn stun(seconds: f32) -> Self {
+        Self {
+            kind: StatusEffectKind::Stun,
+            magnitude: 0.0,
+            seconds_left: seconds,
+            stacking: StatusEffectStacking::Refresh,
+        }
+    }
+    pub fn root(seconds: f32) -> Self {
+        Self {
+            kind: StatusEffectKind::Root,
+            ..Self::stun(seconds)
+        }
+    }
+    pub fn slow(multiplier: f32, seconds: f32) -> Self {
+        Self {
+            kind: StatusEffectKind::Slow,
+            magnitude: multiplier,
+            ..Self::stun(seconds)
+        }
+    }
+    pub fn poison(damage_per_second: f32, seconds: f32) -> Self {
+        Self {
+            kind: StatusEffectKind::Poison,
+            magnitude: damage_per_second,
+            seconds_left: seconds,
+            stacking: StatusEffectStacking::Stack { max_stacks: 5 },
+        }
+    }
+    pub fn burn(damage_per_second: f32, seconds: f32) -> Self {
+        Self {
+            kind: StatusEffectKind::Burn,
+            magnitude: damage_per_second,
+            ..Self::stun(seconds)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    pub immunities: EnumFlags<StatusEffectKind>,
+    accumulated_damage: f32,
+    seconds_since_damage_tick: f32,
+}
+
+impl StatusEffects {
+    const DAMAGE_TICK_INTERVAL: f32 = 0.5;
+
+    pub fn apply(&mut self, status_effect: StatusEffect) {
+        if self.immunities.is_set(&status_effect.kind) {
+            return;
+        }
+        let stacks = self
+            .effects
+            .iter()
+            .filter(|effect| effect.kind == status_effect.kind)
+            .count();
+        let mut same_kind = self
+            .effects
+            .iter_mut()
+            .filter(|effect| effect.kind == status_effect.kind);
+        match status_effect.stacking {
+            StatusEffectStacking::Refresh => {
+                if let Some(effect) = same_kind.next() {
+                    effect.magnitude = status_effect.magnitude;
+                    effect.seconds_left = effect.seconds_left.max(status_effect.seconds_left);
+                    return;
+                }
+            }
+            StatusEffectStacking::Stack { max_stacks } => {
+                if stacks >= max_stacks {
+                    if let Some(oldest) = same_kind
+                        .min_by(|a, b| a.seconds_left.partial_cmp(&b.seconds_left).unwrap())
+                    {
+                        oldest.seconds_left = status_effect.seconds_left;
+                    }
+                    return;
+                }
+            }
+        }
+        self.effects.push(status_effect);
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    pub fn is_rooted(&self) -> bool {
+        self.has(StatusEffectKind::Stun) || self.has(StatusEffectKind::Root)
+    }
+
+    pub fn get_speed_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.kind == StatusEffectKind::Slow)
+            .map(|effect| effect.magnitude)
+            .product()
+    }
+
+    fn get_damage_per_second(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|effect| {
+                matches!(
+                    effect.kind,
+                    StatusEffectKind::Poison | StatusEffectKind::Burn
+                )
+            })
+            .map(|effect| effect.magnitude)
+            .sum()
+    }
+}
+
+impl StatusEffects {
+    pub fn update(update_args: &mut UpdateArgs) {
+        let entity = &mut update_args.entity_instance.entity;
+        let status_effects = &mut entity.status_effects;
+        let damage_per_second = status_effects.get_damage_per_second();
+        status_effects.accumulated_damage += damage_per_second * update_args.dt;
+        status_effects.seconds_since_damage_tick += update_args.dt;
+        // Damage is dealt in ticks, as every deal_damage restarts the damage animation
+        if status_effects.accumulated_damage > 0.0
+            && (status_effects.seconds_since_damage_tick >= Self::DAMAGE_TICK_INTERVAL
+                || damage_per_second == 0.0)
+        {
+            entity.health.deal_damage(status_effects.accumulated_damage);
+            status_effects.accumulated_damage = 0.0;
+            status_effects.seconds_since_damage_tick = 0.0;
+        }
+        status_effects.effects.retain_mut(|effect| {
+            effect.seconds_left -= update_args.dt;
+            effect.seconds_left > 0.0
+        });
+    }
+}
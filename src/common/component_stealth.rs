@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::EntityTag,
+    enum_flags::{flags, EnumFlags},
+    ids::EntityId,
+    update_args::UpdateArgs,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Concealment {
+    /// Hidden until revealed by a detector
+    Stealthed,
+    /// Hidden until it attacks for the first time
+    Camouflaged { has_attacked: bool },
+    /// Hidden until more than `capacity` enemy ground units have come across it
+    HiddenForEncounters {
+        capacity: u32,
+        encountered_by: HashSet<EntityId>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stealth {
+    pub concealment: Concealment,
+    /// Targets it attacks while hidden, without giving itself away
+    pub can_target_while_hidden: EnumFlags<EntityTag>,
+    /// Time left until a detector's reveal wears off
+    pub seconds_revealed: f32,
+}
+
+impl Stealth {
+    fn new(concealment: Concealment, can_target_while_hidden: EnumFlags<EntityTag>) -> Self {
+        Self {
+            concealment,
+            can_target_while_hidden,
+            seconds_revealed: 0.0,
+        }
+    }
+    /// Sneaks past units, attacking only buildings
+    pub fn stealthed() -> Self {
+        Self::new(
+            Concealment::Stealthed,
+            flags![EntityTag::Tower, EntityTag::Base],
+        )
+    }
+    /// Lies in ambush, its first attack gives it away
+    pub fn camouflaged() -> Self {
+        Self::new(
+            Concealment::Camouflaged {
+                has_attacked: false,
+            },
+            flags![
+                EntityTag::Base,
+                EntityTag::Tower,
+                EntityTag::Unit,
+                EntityTag::FlyingUnit
+            ],
+        )
+    }
+    pub fn hidden_for_encounters(capacity: u32) -> Self {
+        Self::new(
+            Concealment::HiddenForEncounters {
+                capacity,
+                encountered_by: HashSet::new(),
+            },
+            flags![EntityTag::Tower, EntityTag::Base],
+        )
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        if self.seconds_revealed > 0.0 {
+            return false;
+        }
+        match &self.concealment {
+            Concealment::Stealthed => true,
+            Concealment::Camouflaged { has_attacked } => !has_attacked,
+            Concealment::HiddenForEncounters {
+                capacity,
+                encountered_by,
+            } => encountered_by.len() <= *capacity as usize,
+        }
+    }
+    /// Called when an enemy looks for targets, counting it as an encounter
+    pub fn can_hide_from(&mut self, entity_id: EntityId, entity_tag: EntityTag) -> bool {
+        if let Concealment::HiddenForEncounters { encountered_by, .. } = &mut self.concealment {
+            if entity_tag == EntityTag::Unit {
+                encountered_by.insert(entity_id);
+            }
+        }
+        self.is_hidden()
+    }
+    pub fn reveal(&mut self, seconds: f32) {
+        self.seconds_revealed = self.seconds_revealed.max(seconds);
+    }
+    pub fn on_attack(&mut self) {
+        if let Concealment::Camouflaged { has_attacked } = &mut self.concealment {
+            *has_attacked = true;
+        }
+    }
+}
+
+impl Stealth {
+    pub fn update(update_args: &mut UpdateArgs) {
+        if let Some(stealth) = &mut update_args.entity_instance.entity.stealth {
+            stealth.seconds_revealed = (stealth.seconds_revealed - update_args.dt).max(0.0);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    component_attack::TargetPool,
+    component_trigger::{Trigger, TriggerEvent},
+    entity::EntityTag,
+    enum_flags::{flags, EnumFlags},
+    find_target::find_entities_in_range,
+    update_args::UpdateArgs,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trap {
+    pub trigger_range: f32,
+}
+
+impl Trap {
+    pub fn new(trigger_range: f32) -> Self {
+        Self { trigger_range }
+    }
+}
+
+impl Trap {
+    /// Fires `OnSprung` when an enemy ground unit steps within range. Flying units pass over
+    pub fn update(update_args: &mut UpdateArgs) {
+        let entity_instance = &update_args.entity_instance;
+        let Some(trap) = &entity_instance.entity.trap else {
+            return;
+        };
+        let owner = entity_instance.owner;
+        let players = &update_args.dynamic_game_state.players;
+        let is_sprung = !find_entities_in_range(
+            entity_instance.pos,
+            trap.trigger_range,
+            flags![EntityTag::Unit],
+            &mut update_args.dynamic_game_state.entities,
+            move |other| TargetPool::Enemies.in_pool(players, owner, other.owner),
+        )
+        .is_empty();
+        if is_sprung {
+            Trigger::fire(TriggerEvent::OnSprung, update_args);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    component_attack::{AttackRange, DamageType, TargetPool},
+    component_movement::{PathState, PathTargetSetter},
+    component_status_effects::StatusEffect,
+    entity::{EntityState, EntityTag},
+    entity_blueprint::EntityBlueprint,
+    enum_flags::EnumFlags,
+    find_target::find_entities_in_range,
+    update_args::UpdateArgs,
+    world::world_get_closest_spawnpoint,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TriggerEvent {
+    OnSpawn,
+    OnDeath,
+    OnAttack,
+    /// Discrete damage only, continuous damage does not fire this
+    OnDamaged,
+    OnKill,
+    /// An enemy ground unit stepped on the trap
+    OnSprung,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TriggerEffect {
+    /// Spawned units continue along the path of the triggering entity
+    SpawnEntities {
+        entity_blueprint: EntityBlueprint,
+        count: usize,
+    },
+    /// Damages enemies in range of the triggering entity
+    Damage {
+        damage: f32,
+        damage_type: DamageType,
+        range: AttackRange,
+        can_target: EnumFlags<EntityTag>,
+    },
+    /// Applies the status effect to enemies in range of the triggering entity
+    ApplyStatusEffect {
+        status_effect: StatusEffect,
+        range: AttackRange,
+        can_target: EnumFlags<EntityTag>,
+    },
+    SelfDestruct,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trigger {
+    pub event: TriggerEvent,
+    pub effect: TriggerEffect,
+}
+
+impl Trigger {
+    pub fn new(event: TriggerEvent, effect: TriggerEffect) -> Self {
+        Self { event, effect }
+    }
+}
+
+impl Trigger {
+    /// Fires the events detected from the entity's own state. Attack, death and kill are fired
+    /// where they happen
+    pub fn update(update_args: &mut UpdateArgs) {
+        if update_args.entity_instance.state == EntityState::SpawnFrame {
+            Trigger::fire(TriggerEvent::OnSpawn, update_args);
+        }
+        if std::mem::take(&mut update_args.entity_instance.entity.health.was_hit) {
+            Trigger::fire(TriggerEvent::OnDamaged, update_args);
+        }
+    }
+
+    pub fn fire(event: TriggerEvent, update_args: &mut UpdateArgs) {
+        let effects: Vec<TriggerEffect> = update_args
+            .entity_instance
+            .entity
+            .triggers
+            .iter()
+            .filter(|trigger| trigger.event == event)
+            .map(|trigger| trigger.effect.clone())
+            .collect();
+        for effect in effects {
+            effect.exec(update_args);
+        }
+    }
+}
+
+impl TriggerEffect {
+    fn exec(&self, update_args: &mut UpdateArgs) {
+        let entity_instance = &mut update_args.entity_instance;
+        match self {
+            TriggerEffect::SpawnEntities {
+                entity_blueprint,
+                count,
+            } => {
+                let path_state = entity_instance
+                    .entity
+                    .movement
+                    .as_ref()
+                    .and_then(|movement| movement.path_target_setter.as_ref())
+                    .and_then(|path_target_setter| path_target_setter.path_state.clone())
+                    .or_else(|| {
+                        let direction = update_args
+                            .dynamic_game_state
+                            .players
+                            .get(&entity_instance.owner)?
+                            .direction
+                            .clone();
+                        world_get_closest_spawnpoint(
+                            update_args.static_game_state,
+                            entity_instance.pos,
+                            direction,
+                        )
+                        .map(PathState::from)
+                    });
+                for _ in 0..*count {
+                    let mut entity = entity_blueprint.create();
+                    if let Some(movement) = entity
+                        .movement
+                        .as_mut()
+                        .filter(|movement| movement.flight_target_setter.is_none())
+                    {
+                        movement.path_target_setter = Some(PathTargetSetter {
+                            path_state: path_state.clone(),
+                        });
+                    }
+                    update_args
+                        .dynamic_game_state
+                        .entities
+                        .spawn(entity.instantiate(entity_instance.owner, entity_instance.pos));
+                }
+            }
+            TriggerEffect::Damage {
+                damage,
+                damage_type,
+                range,
+                can_target,
+            } => {
+                let owner = entity_instance.owner;
+                let players = &update_args.dynamic_game_state.players;
+                for target in find_entities_in_range(
+                    entity_instance.pos,
+                    range.to_f32(entity_instance.entity.radius),
+                    can_target.clone(),
+                    &mut update_args.dynamic_game_state.entities,
+                    move |other| TargetPool::Enemies.in_pool(players, owner, other.owner),
+                ) {
+                    target.entity.health.deal_typed_damage(*damage, damage_type);
+                    target.entity.health.last_damaged_by = Some(entity_instance.id);
+                }
+            }
+            TriggerEffect::ApplyStatusEffect {
+                status_effect,
+                range,
+                can_target,
+            } => {
+                let owner = entity_instance.owner;
+                let players = &update_args.dynamic_game_state.players;
+                for target in find_entities_in_range(
+                    entity_instance.pos,
+                    range.to_f32(entity_instance.entity.radius),
+                    can_target.clone(),
+                    &mut update_args.dynamic_game_state.entities,
+                    move |other| TargetPool::Enemies.in_pool(players, owner, other.owner),
+                ) {
+                    target.entity.status_effects.apply(status_effect.clone());
+                }
+            }
+            TriggerEffect::SelfDestruct => {
+                entity_instance.state = EntityState::Dead;
+            }
+        }
+    }
+}
@@ -6,4 +6,10 @@ pub const DEFAULT_UNIT_DETECTION_RADIUS: f32 = 200.0;
 pub const UNIT_RADIUS: f32 = 36.0;
 pub const BUILDING_RADIUS: f32 = 64.0;
 pub const PROJECTILE_RADIUS: f32 = 12.0;
+pub const ZONE_RADIUS: f32 = 128.0;
+pub const TRAP_RADIUS: f32 = 32.0;
+/// How long a detected entity stays revealed after leaving the detection range
+pub const REVEAL_SECONDS: f32 = 0.5;
+/// How high above their shadow flying units are drawn
+pub const FLYING_ALTITUDE: f32 = 40.0;
 pub const SCROLL_SENSITIVITY: f32 = 0.0005;
@@ -1,5 +1,5 @@
-use crate::{rect_transform::RectTransform, sprites::Sprites};
 use crate::card::Card;
+use crate::{rect_transform::RectTransform, sprites::Sprites};
 use macroquad::{
     color::{Color, WHITE},
     math::Vec2,
@@ -8,7 +8,6 @@ use macroquad::{
     texture::{draw_texture_ex, DrawTextureParams},
 };
 
-
 #[allow(dead_code)]
 pub enum TextOriginX {
     Left,
@@ -132,11 +131,11 @@ pub fn draw_progress_bar(
 pub fn draw_card(card: &Card, transform: &RectTransform, alpha: f32, sprites: &Sprites) {
     let texture = sprites.get_card_texture(card);
     #[rustfmt::skip]
-    let offset_x 
+    let offset_x
         = transform.offset.x * transform.w * f32::cos(-transform.rotation)
         + transform.offset.y * transform.h * f32::sin(-transform.rotation);
     #[rustfmt::skip]
-    let offset_y 
+    let offset_y
         = transform.offset.x * transform.w * f32::sin(-transform.rotation)
         + transform.offset.y * transform.h * f32::cos(-transform.rotation);
     draw_texture_ex(
@@ -150,4 +149,4 @@ pub fn draw_card(card: &Card, transform: &RectTransform, alpha: f32, sprites: &S
             ..Default::default()
         },
     )
-}
\ No newline at end of file
+}
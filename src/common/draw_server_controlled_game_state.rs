@@ -1,14 +1,19 @@
+use crate::component_buff_source::AuraEffect;
+use crate::component_status_effects::StatusEffectKind;
+use crate::config::FLYING_ALTITUDE;
 use crate::debug_draw_config::DebugDrawConfig;
-use crate::entity::{EntityInstance, EntityTag};
+use crate::entity::{AbilityFlag, Entity, EntityInstance, EntityTag};
 use crate::game_state::{
     DynamicGameState, SemiStaticGameState, ServerControlledGameState, StaticGameState,
 };
 use crate::sprites::Sprites;
 use crate::world::{BuildingLocation, Zoning};
 use itertools::Itertools;
-use macroquad::color::{Color, GRAY, LIGHTGRAY, PINK, RED, WHITE};
+use macroquad::color::{
+    Color, BLACK, DARKBROWN, GOLD, GRAY, GREEN, LIGHTGRAY, ORANGE, PINK, RED, SKYBLUE, WHITE,
+};
 use macroquad::math::Vec2;
-use macroquad::shapes::{draw_circle, draw_line, draw_poly};
+use macroquad::shapes::{draw_circle, draw_circle_lines, draw_line, draw_poly, draw_poly_lines};
 use macroquad::texture::{draw_texture_ex, DrawTextureParams};
 
 pub fn draw_minimap(server_controlled_game_state: &ServerControlledGameState) {
@@ -45,7 +50,7 @@ fn draw_minimap_entities(dynamic_game_state: &DynamicGameState, entity_scale: f3
             EntityTag::None => {
                 debug_assert!(false);
             }
-            EntityTag::Bullet => {}
+            EntityTag::Bullet | EntityTag::Zone | EntityTag::Trap => {}
             EntityTag::Tower => {
                 draw_poly_with_border(
                     entity_instance.pos.x,
@@ -98,18 +103,74 @@ pub fn draw_server_controlled_game_state(
         draw_path_nodes(&server_controlled_game_state.static_game_state);
     }
     draw_building_locations(&server_controlled_game_state.semi_static_game_state);
+    draw_zones(&server_controlled_game_state.dynamic_game_state);
     draw_entities(&server_controlled_game_state.dynamic_game_state, sprites);
 }
 
 fn draw_building_locations(semi_static_game_state: &SemiStaticGameState) {
-    for (_id, BuildingLocation { pos, zoning, .. }) in
-        semi_static_game_state.building_locations().iter()
-    {
+    for (_id, building_location) in semi_static_game_state.building_locations().iter() {
+        let BuildingLocation { pos, zoning, .. } = building_location;
         let (poly_sides, color, radius) = match zoning {
             Zoning::Normal => (20, LIGHTGRAY, 16.0),
             Zoning::Commerce => (6, WHITE, 20.0),
         };
-        draw_poly(pos.x, pos.y, poly_sides, radius, 0., color);
+        // Reserved locations are faded until the builder arrives
+        let alpha = if building_location.reservation.is_some() {
+            0.4
+        } else {
+            1.0
+        };
+        draw_poly(
+            pos.x,
+            pos.y,
+            poly_sides,
+            radius,
+            0.,
+            Color { a: alpha, ..color },
+        );
+    }
+}
+
+fn get_zone_color(entity: &Entity) -> Color {
+    match entity
+        .aura_sources
+        .first()
+        .map(|aura_source| &aura_source.effect)
+    {
+        Some(AuraEffect::HealthRegeneration(_)) => GOLD,
+        Some(AuraEffect::ContinuousDamage(_)) => ORANGE,
+        Some(AuraEffect::StatusEffect(status_effect)) => match status_effect.kind {
+            StatusEffectKind::Burn => ORANGE,
+            StatusEffectKind::Poison => GREEN,
+            StatusEffectKind::Slow => DARKBROWN,
+            StatusEffectKind::Stun | StatusEffectKind::Root => SKYBLUE,
+        },
+        Some(AuraEffect::Reveal) | None => GRAY,
+    }
+}
+
+/// Zones are drawn below everything else, since they lie on the ground
+fn draw_zones(dynamic_game_state: &DynamicGameState) {
+    for entity_instance in dynamic_game_state
+        .entities
+        .iter()
+        .filter(|entity_instance| entity_instance.entity.tag == EntityTag::Zone)
+    {
+        let color = get_zone_color(&entity_instance.entity);
+        let radius = entity_instance.entity.radius;
+        draw_circle(
+            entity_instance.pos.x,
+            entity_instance.pos.y,
+            radius,
+            Color { a: 0.3, ..color },
+        );
+        draw_circle_lines(
+            entity_instance.pos.x,
+            entity_instance.pos.y,
+            radius,
+            3.0,
+            color,
+        );
     }
 }
 
@@ -140,11 +201,57 @@ fn draw_entities(dynamic_game_state: &DynamicGameState, sprites: &Sprites) {
                 let height = 2.0 * entity_instance.entity.radius;
                 let width = height * texture.width() / texture.height();
 
+                // Flying units are drawn above their shadow on the ground
+                let draw_pos = if entity_instance.entity.tag == EntityTag::FlyingUnit {
+                    draw_circle(
+                        entity_instance.pos.x,
+                        entity_instance.pos.y + entity_instance.entity.radius * 0.5,
+                        entity_instance.entity.radius * 0.5,
+                        Color { a: 0.3, ..BLACK },
+                    );
+                    entity_instance.pos - Vec2::new(0.0, FLYING_ALTITUDE)
+                } else {
+                    entity_instance.pos
+                };
+
+                // Sprites only come in two colors, so with more players the owner is marked
+                if dynamic_game_state.players.len() > 2 {
+                    draw_circle(
+                        entity_instance.pos.x,
+                        entity_instance.pos.y + entity_instance.entity.radius * 0.5,
+                        entity_instance.entity.radius * 0.6,
+                        Color {
+                            a: 0.6,
+                            ..player.color
+                        },
+                    );
+                }
+
+                if entity_instance.entity.has_ability(AbilityFlag::SpellImmune) {
+                    draw_circle_lines(
+                        draw_pos.x,
+                        draw_pos.y,
+                        entity_instance.entity.radius,
+                        2.0,
+                        GOLD,
+                    );
+                }
+
+                // Only allies receive hidden units, which are drawn see-through
+                let alpha = if entity_instance.entity.is_concealed() {
+                    0.5
+                } else {
+                    1.0
+                };
+
                 draw_texture_ex(
                     texture,
-                    entity_instance.pos.x - entity_instance.entity.radius,
-                    entity_instance.pos.y - entity_instance.entity.radius,
-                    damage_animation_color.unwrap_or(WHITE),
+                    draw_pos.x - entity_instance.entity.radius,
+                    draw_pos.y - entity_instance.entity.radius,
+                    Color {
+                        a: alpha,
+                        ..damage_animation_color.unwrap_or(WHITE)
+                    },
                     DrawTextureParams {
                         dest_size: Some(Vec2 {
                             x: width,
@@ -153,7 +260,40 @@ fn draw_entities(dynamic_game_state: &DynamicGameState, sprites: &Sprites) {
                         flip_x,
                         ..Default::default()
                     },
-                )
+                );
+
+                if let Some(construction) = &entity_instance.entity.construction {
+                    draw_progress_ring(
+                        entity_instance.pos,
+                        entity_instance.entity.radius + 6.0,
+                        construction.progress,
+                        player.color,
+                    );
+                }
+            }
+            EntityTag::Zone => {}
+            // Only the owner receives hidden traps, so they are drawn faded as a reminder
+            EntityTag::Trap => {
+                draw_poly(
+                    entity_instance.pos.x,
+                    entity_instance.pos.y,
+                    6,
+                    entity_instance.entity.radius,
+                    0.0,
+                    Color {
+                        a: 0.4,
+                        ..player.color
+                    },
+                );
+                draw_poly_lines(
+                    entity_instance.pos.x,
+                    entity_instance.pos.y,
+                    6,
+                    entity_instance.entity.radius,
+                    0.0,
+                    2.0,
+                    player.color,
+                );
             }
             EntityTag::Bullet => {
                 draw_circle(
@@ -167,6 +307,29 @@ fn draw_entities(dynamic_game_state: &DynamicGameState, sprites: &Sprites) {
     }
 }
 
+fn draw_progress_ring(center: Vec2, radius: f32, progress: f32, color: Color) {
+    let thickness = 6.0;
+    let segments = 48;
+    draw_circle_lines(
+        center.x,
+        center.y,
+        radius,
+        thickness,
+        Color { a: 0.3, ..BLACK },
+    );
+    let filled_segments = (progress.clamp(0.0, 1.0) * segments as f32).ceil() as usize;
+    // Starts at the top and fills clockwise
+    let point = |i: usize| {
+        let angle =
+            i as f32 / segments as f32 * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+        center + Vec2::from_angle(angle) * radius
+    };
+    for i in 0..filled_segments {
+        let (a, b) = (point(i), point(i + 1));
+        draw_line(a.x, a.y, b.x, b.y, thickness, color);
+    }
+}
+
 fn draw_path_lines(static_game_state: &StaticGameState, line_width: f32) {
     for (_, path) in static_game_state.paths.iter() {
         for ((x1, y1), (x2, y2)) in path.iter().tuple_windows() {
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    component_trigger::{Trigger, TriggerEvent},
     entity::{EntityInstance, EntityState},
     game_loop::{cleanup_entity, update_entity},
     game_state::ServerControlledGameState,
@@ -41,6 +42,10 @@ impl Entities {
         self.0.iter_mut()
     }
 
+    pub fn retain(&mut self, f: impl FnMut(&EntityInstance) -> bool) {
+        self.0.retain(f);
+    }
+
     pub fn spawn(&mut self, entity: EntityInstance) {
         debug_assert!(entity.state == EntityState::CreationFrame);
         let mut entity = entity;
@@ -78,6 +83,7 @@ pub fn update_entities(server_controlled_game_state: &mut ServerControlledGameSt
 }
 
 pub fn remove_dead_entities(server_controlled_game_state: &mut ServerControlledGameState) {
+    let mut dead_entities = Vec::new();
     let mut i = 0;
     while i < server_controlled_game_state
         .dynamic_game_state
@@ -91,13 +97,63 @@ pub fn remove_dead_entities(server_controlled_game_state: &mut ServerControlledG
             .unwrap();
         if entity.state == EntityState::Dead {
             cleanup_entity(entity.id, server_controlled_game_state);
-            server_controlled_game_state
-                .dynamic_game_state
-                .entities
-                .0
-                .swap_remove(i);
+            dead_entities.push(
+                server_controlled_game_state
+                    .dynamic_game_state
+                    .entities
+                    .0
+                    .swap_remove(i),
+            );
         } else {
             i += 1;
         }
     }
+    for mut dead_entity in dead_entities {
+        fire_trigger(
+            server_controlled_game_state,
+            &mut dead_entity,
+            TriggerEvent::OnDeath,
+        );
+        let Some(killer_id) = dead_entity
+            .entity
+            .health
+            .last_damaged_by
+            .filter(|_| dead_entity.entity.health.health <= 0.0)
+        else {
+            continue;
+        };
+        let entities = &mut server_controlled_game_state.dynamic_game_state.entities.0;
+        let Some(killer_idx) = entities.iter().position(|e| e.id == killer_id) else {
+            continue;
+        };
+        let mut killer = entities.swap_remove(killer_idx);
+        fire_trigger(
+            server_controlled_game_state,
+            &mut killer,
+            TriggerEvent::OnKill,
+        );
+        server_controlled_game_state
+            .dynamic_game_state
+            .entities
+            .0
+            .push(killer);
+    }
+}
+
+/// Fires a trigger for an entity that has been taken out of the entity list
+fn fire_trigger(
+    server_controlled_game_state: &mut ServerControlledGameState,
+    entity_instance: &mut EntityInstance,
+    event: TriggerEvent,
+) {
+    Trigger::fire(
+        event,
+        &mut UpdateArgs {
+            static_game_state: &server_controlled_game_state.static_game_state,
+            semi_static_game_state: &mut server_controlled_game_state.semi_static_game_state,
+            dynamic_game_state: &mut server_controlled_game_state.dynamic_game_state,
+            entity_instance,
+            dt: 0.0,
+        },
+    );
 }
@@ -1,13 +1,19 @@
-use crate::buff::ArithmeticBuff;
-use crate::component_buff_source::BuffSource;
+use crate::component_buff_source::{AuraSource, BuffSource};
+use crate::component_construction::Construction;
 use crate::component_health::Health;
 use crate::component_movement::{Movement, MovementSpeed};
-use crate::component_spy::Spy;
+use crate::component_projectile::Projectile;
+use crate::component_spawner::Spawner;
+use crate::component_status_effects::StatusEffects;
+use crate::component_stealth::Stealth;
+use crate::component_trap::Trap;
+use crate::component_trigger::Trigger;
 use crate::config;
 use crate::entity_blueprint::EntityBlueprint;
 use crate::enum_flags::EnumFlags;
 use crate::ids::EntityId;
 use crate::play_target::BuildingLocationTarget;
+use crate::player_modifier::PlayerModifier;
 use crate::serde_defs::Vec2Def;
 use crate::{component_attack::Attack, ids::PlayerId, sprite_id::SpriteId};
 use macroquad::math::Vec2;
@@ -22,6 +28,10 @@ pub enum EntityTag {
     Unit,
     FlyingUnit,
     Bullet,
+    /// Area effect on the ground, never targeted
+    Zone,
+    /// Hidden on a path node until an enemy steps on it, never targeted
+    Trap,
 }
 
 impl From<EntityTag> for usize {
@@ -44,6 +54,9 @@ pub enum EntityState {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum AbilityFlag {
     Protector,
+    SpellImmune,
+    /// Allied ground units can not walk past this unit
+    BlocksAllies,
 }
 impl From<AbilityFlag> for usize {
     fn from(val: AbilityFlag) -> Self {
@@ -63,21 +76,34 @@ pub struct EntityInstance {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Entity {
+    pub blueprint: Option<EntityBlueprint>,
     pub tag: EntityTag,
     pub sprite_id: SpriteId,
     pub radius: f32,
     pub hitbox_radius: f32,
     pub ability_flags: EnumFlags<AbilityFlag>,
     pub health: Health,
+    pub status_effects: StatusEffects,
     pub movement: Option<Movement>,
-    pub spy: Option<Spy>,
+    pub stealth: Option<Stealth>,
     pub buff_sources: Vec<BuffSource>,
-    pub draw_speed_buff: Option<ArithmeticBuff>,
-    pub energy_generation_buff: Option<ArithmeticBuff>,
+    pub aura_sources: Vec<AuraSource>,
+    pub spawner: Option<Spawner>,
+    pub trap: Option<Trap>,
+    pub player_modifiers: Vec<PlayerModifier>,
     pub attacks: Vec<Attack>,
+    pub triggers: Vec<Trigger>,
+    pub projectile: Option<Projectile>,
+    /// The entity that fired this projectile, credited with its kills
+    pub projectile_source_id: Option<EntityId>,
     pub usable_as_spawn_point: bool,
     pub seconds_left_to_live: Option<f32>,
     pub building_to_construct: Option<(BuildingLocationTarget, EntityBlueprint)>,
+    pub construction: Option<Construction>,
+    /// Energy spent on this building, the base for sell refunds
+    pub energy_invested: i32,
+    /// Not replicated to the clients of other players
+    pub hidden: bool,
 }
 
 impl Entity {
@@ -91,6 +117,15 @@ impl Entity {
         }
     }
 
+    pub fn has_ability(&self, ability_flag: AbilityFlag) -> bool {
+        self.ability_flags.is_set(&ability_flag)
+    }
+
+    /// Whether other players currently can't see the entity
+    pub fn is_concealed(&self) -> bool {
+        self.hidden || self.stealth.as_ref().is_some_and(Stealth::is_hidden)
+    }
+
     pub fn default_unit() -> Self {
         Self {
             tag: EntityTag::Unit,
@@ -106,7 +141,30 @@ impl Entity {
             tag: EntityTag::FlyingUnit,
             radius: config::UNIT_RADIUS,
             hitbox_radius: config::UNIT_RADIUS,
-            movement: Some(Movement::new(MovementSpeed::Default)),
+            movement: Some(Movement::new_flying(MovementSpeed::Default)),
+            ..Default::default()
+        }
+    }
+
+    pub fn default_zone() -> Self {
+        Self {
+            tag: EntityTag::Zone,
+            radius: config::ZONE_RADIUS,
+            hitbox_radius: 0.0,
+            health: Health::new(1.0),
+            seconds_left_to_live: Some(10.0),
+            ..Default::default()
+        }
+    }
+
+    pub fn default_trap() -> Self {
+        Self {
+            tag: EntityTag::Trap,
+            radius: config::TRAP_RADIUS,
+            hitbox_radius: 0.0,
+            health: Health::new(1.0),
+            trap: Some(Trap::new(config::TRAP_RADIUS)),
+            hidden: true,
             ..Default::default()
         }
     }
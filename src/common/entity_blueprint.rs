@@ -1,13 +1,24 @@
 use crate::{
     buff::{ArithmeticBuff, Buff, ExtraHealthBuff},
-    component_attack::{Attack, AttackInterval, AttackRange, TargetPool},
-    component_buff_source::{BuffCondition, BuffRange, BuffSource, BuffTargetFilter},
+    component_attack::{
+        Attack, AttackInterval, AttackRange, DamageType, TargetPool, TargetPriority,
+    },
+    component_buff_source::{
+        AuraEffect, AuraSource, BuffCondition, BuffRange, BuffSource, BuffTargetFilter,
+    },
+    component_construction::BuildTime,
     component_health::Health,
     component_movement::{Movement, MovementSpeed},
-    component_spy::Spy,
+    component_projectile::{ProjectileSpec, Trajectory},
+    component_spawner::Spawner,
+    component_status_effects::StatusEffect,
+    component_stealth::Stealth,
+    component_trigger::{Trigger, TriggerEffect, TriggerEvent},
+    config::{TRAP_RADIUS, ZONE_RADIUS},
     entity::{AbilityFlag, Entity, EntityTag},
     entity_filter::EntityFilter,
     enum_flags::{flags, EnumFlags},
+    player_modifier::PlayerModifier,
     sprite_id::SpriteId,
 };
 use serde::{Deserialize, Serialize};
@@ -18,7 +29,9 @@ use strum_macros::EnumIter;
 pub enum EntityBlueprint {
     BasicBuilder,
     HomesickWarrior,
+    ShieldBearer,
     ElfWarrior,
+    VenomArcher,
     OldSwordMaster,
     DemonWolf,
     SmallCriminal,
@@ -27,13 +40,26 @@ pub enum EntityBlueprint {
     RecklessKnight,
     Governor,
     DynamiteMan,
+    Priest,
+    PlagueRat,
     Dragon,
     WarEagle,
     AirBalloon,
+    BurningGround,
+    TarPit,
+    HolyCircle,
+    SpikeTrap,
+    SnareTrap,
+    StunTrap,
     Tower,
     SmallTower,
     Watchtower,
+    Catapult,
+    CannonTower,
     Wall,
+    Shrine,
+    Barracks,
+    GoblinHut,
     Farm,
     TradingPlace,
     IronMine,
@@ -58,7 +84,29 @@ impl EntityBlueprint {
         }
         entity.attacks.first().map(|attack| attack.damage as i32)
     }
+    pub fn get_build_time(&self) -> BuildTime {
+        match self {
+            EntityBlueprint::Watchtower | EntityBlueprint::Catapult | EntityBlueprint::Barracks => {
+                BuildTime::Slow
+            }
+            _ => BuildTime::Regular,
+        }
+    }
+    /// The blueprint a building can be upgraded to in place, and the energy it costs
+    pub fn get_upgrade(&self) -> Option<(EntityBlueprint, i32)> {
+        match self {
+            EntityBlueprint::SmallTower => Some((EntityBlueprint::Tower, 2)),
+            EntityBlueprint::Tower => Some((EntityBlueprint::Watchtower, 3)),
+            _ => None,
+        }
+    }
     pub fn create(&self) -> Entity {
+        Entity {
+            blueprint: Some(self.clone()),
+            ..self.create_entity()
+        }
+    }
+    fn create_entity(&self) -> Entity {
         match self {
             EntityBlueprint::BasicBuilder => Entity {
                 health: Health::new(100.0),
@@ -79,12 +127,35 @@ impl EntityBlueprint {
                 }],
                 ..Entity::default_unit()
             },
+            EntityBlueprint::ShieldBearer => Entity {
+                health: Health::new(400.0).with_armor(0.3),
+                movement: Some(Movement::new(MovementSpeed::Slow)),
+                sprite_id: SpriteId::UnitSwordsman,
+                ability_flags: flags![AbilityFlag::BlocksAllies],
+                attacks: vec![Attack {
+                    damage: 10.0,
+                    ..Attack::default()
+                }],
+                ..Entity::default_unit()
+            },
             EntityBlueprint::ElfWarrior => Entity {
                 health: Health::new(100.0),
                 sprite_id: SpriteId::UnitElfWarrior,
                 attacks: vec![Attack {
                     damage: 10.0,
                     attack_interval: AttackInterval::Fast,
+                    damage_type: DamageType::Piercing,
+                    damage_multipliers: vec![(EntityTag::FlyingUnit, 1.5)],
+                    ..Attack::default_ranged()
+                }],
+                ..Entity::default_unit()
+            },
+            EntityBlueprint::VenomArcher => Entity {
+                health: Health::new(100.0),
+                sprite_id: SpriteId::UnitArcher,
+                attacks: vec![Attack {
+                    damage: 5.0,
+                    status_effects: vec![StatusEffect::poison(5.0, 4.0)],
                     ..Attack::default_ranged()
                 }],
                 ..Entity::default_unit()
@@ -93,6 +164,7 @@ impl EntityBlueprint {
                 health: Health::new(200.0),
                 movement: Some(Movement::new(MovementSpeed::VerySlow)),
                 sprite_id: SpriteId::UnitOldSwordMaster,
+                ability_flags: flags![AbilityFlag::SpellImmune],
                 attacks: vec![Attack {
                     damage: 50.0,
                     ..Attack::default()
@@ -134,9 +206,10 @@ impl EntityBlueprint {
                 sprite_id: SpriteId::UnitSpy,
                 attacks: vec![Attack {
                     damage: 20.0,
+                    target_priority: TargetPriority::LowestHealth,
                     ..Attack::default()
                 }],
-                spy: Some(Spy::new(2)),
+                stealth: Some(Stealth::hidden_for_encounters(2)),
                 ..Entity::default_unit()
             },
             EntityBlueprint::RecklessKnight => Entity {
@@ -172,10 +245,43 @@ impl EntityBlueprint {
                 sprite_id: SpriteId::UnitDynamiteMan,
                 attacks: vec![Attack {
                     damage: 300.0,
+                    damage_type: DamageType::Siege,
+                    damage_multipliers: vec![(EntityTag::Tower, 3.0), (EntityTag::Base, 3.0)],
                     multi_attack_damage_range: Some(AttackRange::Default),
-                    self_destruct: true,
+                    target_priority: TargetPriority::BuildingsFirst,
+                    ..Attack::default()
+                }],
+                triggers: vec![Trigger::new(
+                    TriggerEvent::OnAttack,
+                    TriggerEffect::SelfDestruct,
+                )],
+                ..Entity::default_unit()
+            },
+            EntityBlueprint::Priest => Entity {
+                health: Health::new(100.0),
+                sprite_id: SpriteId::UnitPriest,
+                attacks: vec![Attack {
+                    damage: 15.0,
+                    ..Attack::default_heal()
+                }],
+                ..Entity::default_unit()
+            },
+            EntityBlueprint::PlagueRat => Entity {
+                health: Health::new(100.0),
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                sprite_id: SpriteId::UnitDemonPig,
+                attacks: vec![Attack {
+                    damage: 5.0,
                     ..Attack::default()
                 }],
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::ContinuousDamage(5.0),
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Default),
+                        pool_filter: Some(TargetPool::Enemies),
+                        tag_filter: Some(flags![EntityTag::Unit, EntityTag::FlyingUnit]),
+                    },
+                }],
                 ..Entity::default_unit()
             },
             EntityBlueprint::WarEagle => Entity {
@@ -189,7 +295,7 @@ impl EntityBlueprint {
             },
             EntityBlueprint::AirBalloon => Entity {
                 health: Health::new(400.0),
-                movement: Some(Movement::new(MovementSpeed::Slow)),
+                movement: Some(Movement::new_flying(MovementSpeed::Slow)),
                 sprite_id: SpriteId::UnitAirBalloon,
                 attacks: vec![Attack {
                     damage: 20.0,
@@ -198,7 +304,7 @@ impl EntityBlueprint {
                 ..Entity::default_flying_unit()
             },
             EntityBlueprint::Dragon => Entity {
-                health: Health::new(400.0),
+                health: Health::new(400.0).with_magic_resistance(0.5),
                 sprite_id: SpriteId::UnitDragon,
                 attacks: vec![Attack {
                     damage: 40.0,
@@ -206,6 +312,83 @@ impl EntityBlueprint {
                 }],
                 ..Entity::default_flying_unit()
             },
+            EntityBlueprint::BurningGround => Entity {
+                seconds_left_to_live: Some(8.0),
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::StatusEffect(StatusEffect::burn(20.0, 1.0)),
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Custom(ZONE_RADIUS)),
+                        pool_filter: Some(TargetPool::Enemies),
+                        tag_filter: Some(flags![EntityTag::Unit]),
+                    },
+                }],
+                ..Entity::default_zone()
+            },
+            EntityBlueprint::TarPit => Entity {
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::StatusEffect(StatusEffect::slow(0.4, 0.5)),
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Custom(ZONE_RADIUS)),
+                        pool_filter: Some(TargetPool::Enemies),
+                        tag_filter: Some(flags![EntityTag::Unit]),
+                    },
+                }],
+                ..Entity::default_zone()
+            },
+            EntityBlueprint::HolyCircle => Entity {
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::HealthRegeneration(15.0),
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Custom(ZONE_RADIUS)),
+                        pool_filter: Some(TargetPool::Allies),
+                        tag_filter: Some(flags![EntityTag::Unit]),
+                    },
+                }],
+                ..Entity::default_zone()
+            },
+            EntityBlueprint::SpikeTrap => Entity {
+                triggers: vec![
+                    Trigger::new(
+                        TriggerEvent::OnSprung,
+                        TriggerEffect::Damage {
+                            damage: 150.0,
+                            damage_type: DamageType::Physical,
+                            range: AttackRange::Custom(TRAP_RADIUS),
+                            can_target: flags![EntityTag::Unit],
+                        },
+                    ),
+                    Trigger::new(TriggerEvent::OnSprung, TriggerEffect::SelfDestruct),
+                ],
+                ..Entity::default_trap()
+            },
+            EntityBlueprint::SnareTrap => Entity {
+                triggers: vec![
+                    Trigger::new(
+                        TriggerEvent::OnSprung,
+                        TriggerEffect::ApplyStatusEffect {
+                            status_effect: StatusEffect::root(5.0),
+                            range: AttackRange::Custom(TRAP_RADIUS),
+                            can_target: flags![EntityTag::Unit],
+                        },
+                    ),
+                    Trigger::new(TriggerEvent::OnSprung, TriggerEffect::SelfDestruct),
+                ],
+                ..Entity::default_trap()
+            },
+            EntityBlueprint::StunTrap => Entity {
+                triggers: vec![
+                    Trigger::new(
+                        TriggerEvent::OnSprung,
+                        TriggerEffect::ApplyStatusEffect {
+                            status_effect: StatusEffect::stun(2.0),
+                            range: AttackRange::Custom(TRAP_RADIUS * 2.0),
+                            can_target: flags![EntityTag::Unit],
+                        },
+                    ),
+                    Trigger::new(TriggerEvent::OnSprung, TriggerEffect::SelfDestruct),
+                ],
+                ..Entity::default_trap()
+            },
             EntityBlueprint::Tower => Entity {
                 health: Health::new(500.0),
                 sprite_id: SpriteId::BuildingTower,
@@ -238,6 +421,7 @@ impl EntityBlueprint {
                 sprite_id: SpriteId::BuildingTower,
                 attacks: vec![Attack {
                     damage: 10.0,
+                    target_priority: TargetPriority::FurthestAlongPath,
                     ..Attack::default_ranged_tower()
                 }],
                 buff_sources: vec![BuffSource {
@@ -249,30 +433,98 @@ impl EntityBlueprint {
                         tag_filter: None,
                     }),
                 }],
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::Reveal,
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Default),
+                        pool_filter: Some(TargetPool::Enemies),
+                        tag_filter: None,
+                    },
+                }],
+                ..Entity::default_tower()
+            },
+            EntityBlueprint::Catapult => Entity {
+                health: Health::new(300.0),
+                sprite_id: SpriteId::BuildingTower,
+                attacks: vec![Attack {
+                    damage: 40.0,
+                    attack_interval: AttackInterval::VerySlow,
+                    range: AttackRange::Custom(250.0),
+                    can_target: flags![EntityTag::Unit],
+                    projectile: ProjectileSpec {
+                        trajectory: Trajectory::Ballistic,
+                        speed: MovementSpeed::Custom(300.0),
+                        splash_range: Some(AttackRange::Custom(60.0)),
+                        ..ProjectileSpec::default()
+                    },
+                    ..Attack::default_ranged_tower()
+                }],
+                ..Entity::default_tower()
+            },
+            EntityBlueprint::CannonTower => Entity {
+                health: Health::new(400.0),
+                sprite_id: SpriteId::BuildingTower,
+                attacks: vec![Attack {
+                    damage: 30.0,
+                    attack_interval: AttackInterval::Slow,
+                    can_target: flags![EntityTag::Unit],
+                    projectile: ProjectileSpec {
+                        pierce: 2,
+                        ..ProjectileSpec::default()
+                    },
+                    ..Attack::default_ranged_tower()
+                }],
                 ..Entity::default_tower()
             },
 
             EntityBlueprint::Wall => Entity {
-                health: Health::new(1000.0),
+                health: Health::new(1000.0).with_armor(0.5),
                 sprite_id: SpriteId::BuildingTower,
                 ..Entity::default_tower()
             },
+            EntityBlueprint::Shrine => Entity {
+                health: Health::new(300.0),
+                sprite_id: SpriteId::BuildingSpawnpoint,
+                aura_sources: vec![AuraSource {
+                    effect: AuraEffect::HealthRegeneration(10.0),
+                    target_filter: EntityFilter {
+                        range_filter: Some(BuffRange::Default),
+                        pool_filter: Some(TargetPool::Allies),
+                        tag_filter: Some(flags![EntityTag::Unit, EntityTag::FlyingUnit]),
+                    },
+                }],
+                ..Entity::default_tower()
+            },
+            EntityBlueprint::Barracks => Entity {
+                health: Health::new(400.0),
+                sprite_id: SpriteId::BuildingHut,
+                spawner: Some(
+                    Spawner::new(EntityBlueprint::StreetCriminal, 10.0)
+                        .with_max_alive(3)
+                        .with_pause_while_damaged(),
+                ),
+                ..Entity::default_tower()
+            },
+            EntityBlueprint::GoblinHut => Entity {
+                health: Health::new(200.0),
+                sprite_id: SpriteId::BuildingHut,
+                spawner: Some(Spawner::new(EntityBlueprint::SmallCriminal, 5.0).with_max_alive(4)),
+                ..Entity::default_tower()
+            },
             EntityBlueprint::Farm => Entity {
                 health: Health::new(200.0),
                 sprite_id: SpriteId::BuildingFarm,
-                draw_speed_buff: Some(ArithmeticBuff {
-                    multiplier: 1.4,
-                    ..Default::default()
-                }),
+                player_modifiers: vec![PlayerModifier::DrawSpeed(
+                    ArithmeticBuff::new_multiplicative(1.4),
+                )],
                 ..Entity::default_tower()
             },
             EntityBlueprint::TradingPlace => Entity {
                 health: Health::new(200.0),
                 sprite_id: SpriteId::BuildingTradingPlace,
-                energy_generation_buff: Some(ArithmeticBuff {
-                    multiplier: 1.4,
-                    ..Default::default()
-                }),
+                player_modifiers: vec![PlayerModifier::EnergyGeneration(
+                    ArithmeticBuff::new_multiplicative(1.4),
+                )],
                 ..Entity::default_tower()
             },
             EntityBlueprint::IronMine => Entity {
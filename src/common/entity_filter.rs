@@ -2,8 +2,11 @@ use crate::{
     component_attack::TargetPool,
     entity::{EntityInstance, EntityTag},
     enum_flags::EnumFlags,
+    ids::PlayerId,
+    server_player::ServerPlayer,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EntityFilter<Range: Clone + Default> {
@@ -17,30 +20,38 @@ pub trait Tof32 {
 }
 
 impl<Range: Tof32 + Clone + Default> EntityFilter<Range> {
-    fn predicate(&self, source: &EntityInstance, other: &EntityInstance) -> bool {
+    fn predicate(
+        &self,
+        source: &EntityInstance,
+        players: &HashMap<PlayerId, ServerPlayer>,
+        other: &EntityInstance,
+    ) -> bool {
         let out_of_range = self.range_filter.as_ref().is_some_and(|range| {
             (source.pos - other.pos).length_squared() > range.to_f32().powi(2)
         });
         let out_of_pool = self
             .pool_filter
             .as_ref()
-            .is_some_and(|pool| !pool.in_pool(source.owner, other.owner));
-        let out_of_tag_filter = self
-            .tag_filter
-            .as_ref()
-            .is_some_and(|tag_filter| !tag_filter.is_set(&other.entity.tag));
+            .is_some_and(|pool| !pool.in_pool(players, source.owner, other.owner));
+        // Hidden traps are only affected by filters that name them
+        let out_of_tag_filter = match &self.tag_filter {
+            Some(tag_filter) => !tag_filter.is_set(&other.entity.tag),
+            None => other.entity.tag == EntityTag::Trap,
+        };
         !out_of_range && !out_of_pool && !out_of_tag_filter
     }
     pub fn to_fn<'a>(
         &'a self,
         source: &'a EntityInstance,
+        players: &'a HashMap<PlayerId, ServerPlayer>,
     ) -> impl FnMut(&&EntityInstance) -> bool + 'a {
-        move |other| self.predicate(source, other)
+        move |other| self.predicate(source, players, other)
     }
     pub fn to_fn_mut<'a>(
         &'a self,
         source: &'a EntityInstance,
+        players: &'a HashMap<PlayerId, ServerPlayer>,
     ) -> impl FnMut(&&mut EntityInstance) -> bool + 'a {
-        move |other| self.predicate(source, other)
+        move |other| self.predicate(source, players, other)
     }
 }
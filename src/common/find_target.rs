@@ -1,35 +1,120 @@
 use macroquad::math::Vec2;
 
 use crate::{
-    component_attack::Attack,
-    component_spy::Spy,
+    component_attack::{Attack, AttackEffect, TargetPool, TargetPriority},
+    component_movement::get_path_state,
+    component_stealth::Stealth,
     entity::{EntityInstance, EntityTag},
     enum_flags::EnumFlags,
+    game_state::StaticGameState,
     ids::{EntityId, PlayerId},
+    server_player::ServerPlayer,
 };
+use std::collections::HashMap;
 
 pub fn find_target_id_for_attack(
     entity_id: EntityId,
     entity_tag: EntityTag,
     entity_pos: Vec2,
     entity_owner: PlayerId,
-    entity_spy: Option<&Spy>,
+    entity_stealth: Option<&Stealth>,
     range: f32,
     attack: &Attack,
+    static_game_state: &StaticGameState,
+    players: &HashMap<PlayerId, ServerPlayer>,
     other_entities: &mut [EntityInstance],
 ) -> Option<EntityId> {
-    find_targets_for_attack(
+    // The attacker is not among `other_entities`, but may itself be a base
+    let bases: Vec<(PlayerId, Vec2)> = match attack.target_priority {
+        TargetPriority::FurthestAlongPath => other_entities
+            .iter()
+            .filter(|other| other.entity.tag == EntityTag::Base)
+            .map(|other| (other.owner, other.pos))
+            .chain((entity_tag == EntityTag::Base).then_some((entity_owner, entity_pos)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let targets = find_targets_for_attack(
         entity_id,
         entity_tag,
         entity_pos,
         entity_owner,
-        entity_spy,
+        entity_stealth,
         range,
         attack,
+        players,
         other_entities,
-    )
-    .first()
-    .map(|e| e.id)
+    );
+    // Heals always prefer the most damaged target
+    if attack.effect == AttackEffect::Heal {
+        return targets.first().map(|target| target.id);
+    }
+    // Targets are sorted by distance, so ties go to the nearest
+    let mut targets = targets.iter().map(|target| &**target);
+    let compare_f32 = |a: f32, b: f32| a.partial_cmp(&b).unwrap();
+    let target = match attack.target_priority {
+        TargetPriority::Nearest => targets.next(),
+        TargetPriority::LowestHealth => targets
+            .min_by(|a, b| compare_f32(a.entity.health.get_health(), b.entity.health.get_health())),
+        TargetPriority::HighestHealth => targets
+            .min_by(|a, b| compare_f32(b.entity.health.get_health(), a.entity.health.get_health())),
+        TargetPriority::HighestDamage => {
+            targets.min_by(|a, b| compare_f32(get_damage_per_second(b), get_damage_per_second(a)))
+        }
+        TargetPriority::FurthestAlongPath => targets.min_by(|a, b| {
+            compare_f32(
+                get_remaining_distance(static_game_state, players, &bases, a),
+                get_remaining_distance(static_game_state, players, &bases, b),
+            )
+        }),
+        TargetPriority::BuildingsFirst => targets
+            .min_by_key(|target| !matches!(target.entity.tag, EntityTag::Tower | EntityTag::Base)),
+        TargetPriority::KeepCurrentTarget => {
+            let mut targets = targets.peekable();
+            let nearest = targets.peek().copied();
+            targets
+                .find(|target| Some(target.id) == attack.current_target_id)
+                .or(nearest)
+        }
+    };
+    target.map(|target| target.id)
+}
+
+fn get_damage_per_second(entity_instance: &EntityInstance) -> f32 {
+    entity_instance
+        .entity
+        .attacks
+        .iter()
+        .map(|attack| attack.get_damage_per_second())
+        .sum()
+}
+
+/// Flyers head straight for the nearest enemy base, so their remaining distance is measured in a
+/// straight line. Other entities that don't walk a path are treated as not having advanced at all
+fn get_remaining_distance(
+    static_game_state: &StaticGameState,
+    players: &HashMap<PlayerId, ServerPlayer>,
+    bases: &[(PlayerId, Vec2)],
+    entity_instance: &EntityInstance,
+) -> f32 {
+    if let Some(path_state) = get_path_state(&entity_instance.entity) {
+        return path_state.get_remaining_distance(static_game_state, entity_instance.pos);
+    }
+    if entity_instance
+        .entity
+        .movement
+        .as_ref()
+        .is_some_and(|movement| movement.flight_target_setter.is_some())
+    {
+        return bases
+            .iter()
+            .filter(|(base_owner, _)| {
+                TargetPool::Enemies.in_pool(players, entity_instance.owner, *base_owner)
+            })
+            .map(|(_, base_pos)| base_pos.distance(entity_instance.pos))
+            .fold(f32::INFINITY, f32::min);
+    }
+    f32::INFINITY
 }
 
 pub fn find_target_ids_for_attack(
@@ -37,9 +122,10 @@ pub fn find_target_ids_for_attack(
     entity_tag: EntityTag,
     entity_pos: Vec2,
     entity_owner: PlayerId,
-    entity_spy: Option<&Spy>,
+    entity_stealth: Option<&Stealth>,
     range: f32,
     attack: &Attack,
+    players: &HashMap<PlayerId, ServerPlayer>,
     other_entities: &mut [EntityInstance],
 ) -> Vec<EntityId> {
     find_targets_for_attack(
@@ -47,9 +133,10 @@ pub fn find_target_ids_for_attack(
         entity_tag,
         entity_pos,
         entity_owner,
-        entity_spy,
+        entity_stealth,
         range,
         attack,
+        players,
         other_entities,
     )
     .iter()
@@ -62,42 +149,58 @@ pub fn find_targets_for_attack<'a>(
     entity_tag: EntityTag,
     entity_pos: Vec2,
     entity_owner: PlayerId,
-    entity_spy: Option<&'a Spy>,
+    entity_stealth: Option<&'a Stealth>,
     range: f32,
     attack: &Attack,
+    players: &'a HashMap<PlayerId, ServerPlayer>,
     other_entities: &'a mut [EntityInstance],
 ) -> Vec<&'a mut EntityInstance> {
     let attack_target_pool = attack.target_pool.clone();
-    find_entities_in_range(
+    let heals = attack.effect == AttackEffect::Heal;
+    let mut targets = find_entities_in_range(
         entity_pos,
         range,
         attack.can_target.clone(),
         other_entities,
         move |other_entity| {
-            attack_target_pool.in_pool(entity_owner, other_entity.owner)
-                && can_find_target(entity_id, entity_tag.clone(), entity_spy, other_entity)
+            attack_target_pool.in_pool(players, entity_owner, other_entity.owner)
+                && can_find_target(entity_id, entity_tag.clone(), entity_stealth, other_entity)
+                && (!heals
+                    || (other_entity.id != entity_id
+                        && other_entity.entity.health.get_missing_health() > 0.0))
         },
-    )
+    );
+    if heals {
+        targets.sort_by(|a, b| {
+            b.entity
+                .health
+                .get_missing_health()
+                .partial_cmp(&a.entity.health.get_missing_health())
+                .unwrap()
+        });
+    }
+    targets
 }
 
 fn can_find_target(
     entity_id: EntityId,
     entity_tag: EntityTag,
-    entity_spy: Option<&Spy>,
+    entity_stealth: Option<&Stealth>,
     other_entity_instance: &mut EntityInstance,
 ) -> bool {
-    if let Some(entity_spy) = entity_spy {
-        if entity_spy.is_hidden()
-            && other_entity_instance.entity.tag != EntityTag::Tower
-            && other_entity_instance.entity.tag != EntityTag::Base
+    if let Some(entity_stealth) = entity_stealth {
+        if entity_stealth.is_hidden()
+            && !entity_stealth
+                .can_target_while_hidden
+                .is_set(&other_entity_instance.entity.tag)
         {
             return false;
         }
     }
-    let Some(spy) = other_entity_instance.entity.spy.as_mut() else {
+    let Some(stealth) = other_entity_instance.entity.stealth.as_mut() else {
         return true;
     };
-    !spy.can_hide_from(entity_id, entity_tag)
+    !stealth.can_hide_from(entity_id, entity_tag)
 }
 
 pub fn find_entities_in_range<'a>(
@@ -1,17 +1,26 @@
 use crate::{
     buff::buff_update_timers,
     component_attack::Attack,
-    component_buff_source::BuffSource,
+    component_buff_source::{AuraSource, BuffSource},
+    component_construction::Construction,
     component_health::Health,
     component_movement::Movement,
+    component_projectile::Projectile,
+    component_spawner::Spawner,
+    component_status_effects::StatusEffects,
+    component_stealth::Stealth,
+    component_trap::Trap,
+    component_trigger::Trigger,
     config::CLOSE_ENOUGH_TO_TARGET,
     entities::{remove_dead_entities, update_entities},
     entity::EntityState,
     find_target::find_targets_for_attack,
     game_state::ServerControlledGameState,
     ids::EntityId,
+    player_modifier::update_player_modifiers,
+    ruleset::FailedConstructionCompensation,
     update_args::UpdateArgs,
-    world::world_place_building,
+    world::{world_place_building, BuildingLocationReservation},
 };
 
 pub fn cleanup_entity(
@@ -26,10 +35,52 @@ pub fn cleanup_entity(
     {
         building_location.entity_id = None;
     }
+    if let Some(reservation) = server_controlled_game_state
+        .semi_static_game_state
+        .building_locations_mut()
+        .values_mut()
+        .find(|building_location| {
+            building_location
+                .reservation
+                .as_ref()
+                .is_some_and(|reservation| reservation.builder_id == entity_id)
+        })
+        .and_then(|building_location| building_location.reservation.take())
+    {
+        compensate_failed_construction(reservation, server_controlled_game_state);
+    }
+}
+
+fn compensate_failed_construction(
+    reservation: BuildingLocationReservation,
+    server_controlled_game_state: &mut ServerControlledGameState,
+) {
+    let ruleset = &server_controlled_game_state.static_game_state.ruleset;
+    let Some(player) = server_controlled_game_state
+        .dynamic_game_state
+        .players
+        .get_mut(&reservation.owner)
+    else {
+        return;
+    };
+    match ruleset.failed_construction_compensation {
+        FailedConstructionCompensation::Nothing => {}
+        FailedConstructionCompensation::RefundEnergy => {
+            player
+                .hand
+                .refund_energy(reservation.energy_paid, ruleset, &player.modifiers)
+        }
+        FailedConstructionCompensation::ReturnCard => {
+            player
+                .hand
+                .return_card(reservation.card, ruleset, &player.modifiers)
+        }
+    }
 }
 
 pub fn update_game_state(server_controlled_game_state: &mut ServerControlledGameState, dt: f32) {
     remove_dead_entities(server_controlled_game_state);
+    update_player_modifiers(&mut server_controlled_game_state.dynamic_game_state, dt);
     for entity_instance in server_controlled_game_state
         .dynamic_game_state
         .entities
@@ -46,7 +97,32 @@ pub fn update_game_state(server_controlled_game_state: &mut ServerControlledGame
 }
 
 pub fn update_entity(update_args: &mut UpdateArgs) {
+    Trigger::update(update_args);
+    if update_args.entity_instance.entity.construction.is_some() {
+        if !matches!(
+            update_args.entity_instance.state,
+            EntityState::CreationFrame | EntityState::SpawnFrame
+        ) {
+            update_args.entity_instance.state = EntityState::Building;
+        }
+        Construction::update(update_args);
+        Health::update(update_args);
+        return;
+    }
     BuffSource::update(update_args);
+    AuraSource::update(update_args);
+    StatusEffects::update(update_args);
+    Stealth::update(update_args);
+    if update_args
+        .entity_instance
+        .entity
+        .status_effects
+        .is_stunned()
+    {
+        Health::update(update_args);
+        return;
+    }
+    Spawner::update(update_args);
 
     let can_attack = update_args
         .entity_instance
@@ -59,9 +135,10 @@ pub fn update_entity(update_args: &mut UpdateArgs) {
                 update_args.entity_instance.entity.tag.clone(),
                 update_args.entity_instance.pos,
                 update_args.entity_instance.owner,
-                update_args.entity_instance.entity.spy.as_ref(),
+                update_args.entity_instance.entity.stealth.as_ref(),
                 attack.get_range(update_args.entity_instance.entity.radius),
                 attack,
+                &update_args.dynamic_game_state.players,
                 &mut update_args.dynamic_game_state.entities,
             )
             .is_empty()
@@ -120,10 +197,12 @@ pub fn update_entity(update_args: &mut UpdateArgs) {
                 if (building_to_construct_pos - update_args.entity_instance.pos).length()
                     < CLOSE_ENOUGH_TO_TARGET
                 {
+                    let mut building = entity_blueprint.create();
+                    Construction::start(&mut building, entity_blueprint.get_build_time());
                     world_place_building(
                         update_args.semi_static_game_state,
                         update_args.dynamic_game_state,
-                        entity_blueprint.create(),
+                        building,
                         &building_spot_target.id,
                         update_args.entity_instance.owner,
                     );
@@ -137,5 +216,7 @@ pub fn update_entity(update_args: &mut UpdateArgs) {
         EntityState::Passive | EntityState::Dead => {}
     }
 
+    Trap::update(update_args);
+    Projectile::update(update_args);
     Health::update(update_args);
 }
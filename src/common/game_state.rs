@@ -1,9 +1,11 @@
 use crate::{
     entities::Entities,
-    ids::{BuildingLocationId, GameId, PathId, PlayerId},
+    entity::EntityTag,
+    ids::{BuildingLocationId, GameId, PathId, PlayerId, TeamId},
     level_config::LevelConfig,
     network::{ServerMessage, ServerMessageData},
-    server_player::ServerPlayer,
+    ruleset::Ruleset,
+    server_player::{are_allies, ServerPlayer},
     world::BuildingLocation,
 };
 use macroquad::math::Vec2;
@@ -12,7 +14,12 @@ use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StaticGameState {
+    pub level_width: i32,
+    pub level_height: i32,
+    pub spawn_point_radius: f32,
+    pub nearby_radius: f32,
     pub paths: HashMap<PathId, Vec<(f32, f32)>>,
+    pub ruleset: Ruleset,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -37,6 +44,39 @@ pub struct DynamicGameState {
     pub players: HashMap<PlayerId, ServerPlayer>,
 }
 
+impl DynamicGameState {
+    /// The state as replicated to the player, without the hidden entities of enemies
+    pub fn visible_to(&self, player_id: PlayerId) -> DynamicGameState {
+        let mut dynamic_game_state = self.clone();
+        dynamic_game_state.entities.retain(|entity_instance| {
+            !entity_instance.entity.is_concealed()
+                || are_allies(&self.players, player_id, entity_instance.owner)
+        });
+        dynamic_game_state
+    }
+
+    /// The team owning all the remaining bases, once every other team has lost theirs
+    pub fn get_winning_team(&self) -> Option<TeamId> {
+        let base_teams = self
+            .entities
+            .iter()
+            .filter(|entity_instance| entity_instance.entity.tag == EntityTag::Base)
+            .map(|entity_instance| {
+                self.players
+                    .get(&entity_instance.owner)
+                    .map(|player| player.team)
+            })
+            .collect::<Option<Vec<TeamId>>>()?;
+        let winning_team = *base_teams.first()?;
+        let has_opponents = self
+            .players
+            .values()
+            .any(|player| player.team != winning_team);
+        (has_opponents && base_teams.iter().all(|team| *team == winning_team))
+            .then_some(winning_team)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GameMetadata {
     pub game_id: GameId,
@@ -60,6 +100,7 @@ impl ServerControlledGameState {
         } else {
             self.game_metadata = server_message.metadata;
             match server_message.data {
+                ServerMessageData::HelloAccepted | ServerMessageData::HelloRejected(_) => {}
                 ServerMessageData::StaticGameState(static_state) => {
                     self.static_game_state = static_state;
                 }
@@ -75,6 +116,10 @@ impl ServerControlledGameState {
     }
 
     pub fn load_level_config(&mut self, level_config: LevelConfig) {
+        self.static_game_state.level_width = level_config.level_width;
+        self.static_game_state.level_height = level_config.level_height;
+        self.static_game_state.spawn_point_radius = level_config.spawn_point_radius;
+        self.static_game_state.nearby_radius = level_config.nearby_radius;
         for path in level_config.paths {
             self.static_game_state.paths.insert(PathId::new(), path);
         }
@@ -86,6 +131,7 @@ impl ServerControlledGameState {
                     pos: Vec2::new(*x, *y),
                     entity_id: None,
                     zoning: zoning.clone(),
+                    reservation: None,
                 },
             );
         }
@@ -1,8 +1,9 @@
 use crate::{
     game_state::{DynamicGameState, StaticGameState},
     ids::PlayerId,
-    level_config::get_prototype_level_config,
+    path_graph::{path_end_idx, BranchPolicy},
     play_target::UnitSpawnpointTarget,
+    world::get_path_pos,
 };
 use macroquad::math::Vec2;
 
@@ -18,12 +19,14 @@ pub fn get_unit_spawnpoints(
         .direction
         .clone();
 
-    dynamic_game_state
+    let mut spawnpoints: Vec<UnitSpawnpointTarget> = dynamic_game_state
         .entities
         .iter()
         .filter_map(|entity_instance| {
-            (entity_instance.owner == player_id && entity_instance.entity.usable_as_spawn_point)
-                .then_some(entity_instance.pos)
+            (entity_instance.owner == player_id
+                && entity_instance.entity.usable_as_spawn_point
+                && entity_instance.entity.construction.is_none())
+            .then_some(entity_instance.pos)
         })
         .flat_map(|pos| {
             static_game_state
@@ -40,9 +43,7 @@ pub fn get_unit_spawnpoints(
                             )
                         })
                         .min_by(|(_, _, a), (_, _, b)| a.partial_cmp(b).unwrap())
-                        .filter(|(_, _, dist)| {
-                            dist < &get_prototype_level_config().spawn_point_radius
-                        })
+                        .filter(|(_, _, dist)| dist < &static_game_state.spawn_point_radius)
                         .map(|(path_id, path_idx, _)| (path_id, path_idx))
                 })
         })
@@ -50,6 +51,23 @@ pub fn get_unit_spawnpoints(
             path_id,
             path_idx,
             direction: direction.clone(),
+            branch_policy: BranchPolicy::default(),
         })
-        .collect()
+        .collect();
+
+    // Paths meeting at a junction share the node, so keep the one leading away from it
+    spawnpoints.sort_by_key(|spawnpoint| {
+        let path = static_game_state.paths.get(&spawnpoint.path_id).unwrap();
+        spawnpoint.path_idx == path_end_idx(path, &direction)
+    });
+    let mut positions: Vec<Vec2> = Vec::new();
+    spawnpoints.retain(|spawnpoint| {
+        let pos = get_path_pos(static_game_state, spawnpoint.path_id, spawnpoint.path_idx);
+        if positions.contains(&pos) {
+            return false;
+        }
+        positions.push(pos);
+        true
+    });
+    spawnpoints
 }
@@ -1,7 +1,7 @@
-use crate::buff::{apply_arithmetic_buffs, ArithmeticBuff};
 use crate::card::{Card, CardInstance};
-use crate::gameplay_config::{CARD_DRAW_PER_SECOND, ENERGY_PER_SECOND, MAX_HAND_SIZE};
 use crate::ids::CardInstanceId;
+use crate::player_modifier::PlayerModifiers;
+use crate::ruleset::Ruleset;
 use crate::vector::{pop_where, shuffle_vec};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -36,8 +36,8 @@ impl Hand {
         }
     }
 
-    pub fn draw(&mut self) -> Option<CardInstance> {
-        if self.cards.len() >= MAX_HAND_SIZE {
+    pub fn draw(&mut self, ruleset: &Ruleset, modifiers: &PlayerModifiers) -> Option<CardInstance> {
+        if self.cards.len() >= modifiers.max_hand_size(ruleset) {
             return None;
         }
         if self.deck.is_empty() {
@@ -50,55 +50,81 @@ impl Hand {
         Some(card)
     }
 
-    pub fn step(
-        &mut self,
-        dt: f32,
-        draw_speed_buffs: &[ArithmeticBuff],
-        energy_generation_buffs: &[ArithmeticBuff],
-    ) {
-        self.card_draw_counter +=
-            dt * apply_arithmetic_buffs(CARD_DRAW_PER_SECOND, draw_speed_buffs);
-        self.energy_counter +=
-            dt * apply_arithmetic_buffs(ENERGY_PER_SECOND, energy_generation_buffs);
+    pub fn step(&mut self, dt: f32, ruleset: &Ruleset, modifiers: &PlayerModifiers) {
+        self.card_draw_counter += dt * modifiers.card_draw_per_second(ruleset);
+        self.energy_counter += dt * modifiers.energy_per_second(ruleset);
 
         if self.card_draw_counter >= 1.0 {
-            self.draw();
+            if self.draw(ruleset, modifiers).is_some() {
+                self.refund_energy(modifiers.draw_bonus_energy(), ruleset, modifiers);
+            }
             self.card_draw_counter = 0.0;
         }
         if self.energy_counter >= 1.0 {
-            self.energy = (self.energy + 1).min(10);
+            self.refund_energy(1, ruleset, modifiers);
             self.energy_counter = 0.0;
         }
+        if ruleset.infinite_energy {
+            self.energy = modifiers.max_energy(ruleset);
+        }
+    }
+
+    pub fn refund_energy(&mut self, energy: i32, ruleset: &Ruleset, modifiers: &PlayerModifiers) {
+        self.energy = (self.energy + energy).min(modifiers.max_energy(ruleset));
+    }
+
+    /// Moves a played card back into the hand, or on top of the deck if the hand is full. The card
+    /// may have been shuffled back into the deck since it was played, and is created anew if it
+    /// can't be found at all
+    pub fn return_card(&mut self, card: Card, ruleset: &Ruleset, modifiers: &PlayerModifiers) {
+        let card_instance = pop_where(&mut self.played, |card_instance| card_instance.card == card)
+            .or_else(|| pop_where(&mut self.deck, |card_instance| card_instance.card == card))
+            .unwrap_or_else(|| CardInstance {
+                id: CardInstanceId::new(),
+                card,
+            });
+        if self.cards.len() < modifiers.max_hand_size(ruleset) {
+            self.cards.push(card_instance);
+        } else {
+            self.deck.push(card_instance);
+        }
+    }
+
+    pub fn deal_starting_hand(&mut self, ruleset: &Ruleset) {
+        self.energy = ruleset.starting_energy;
+        for _ in 0..ruleset.starting_hand_size {
+            self.draw(ruleset, &PlayerModifiers::default());
+        }
     }
 
-    pub fn try_get(&mut self, card_id: CardInstanceId) -> Option<Card> {
+    pub fn try_get(
+        &mut self,
+        card_id: CardInstanceId,
+        modifiers: &PlayerModifiers,
+    ) -> Option<Card> {
         self.cards
             .iter()
             .find(|card_instance| {
                 card_instance.id == card_id
-                    && card_instance.card.get_card_data().energy_cost <= self.energy
+                    && modifiers.energy_cost(&card_instance.card) <= self.energy
             })
             .map(|card_instance| card_instance.card.clone())
     }
 
-    pub fn play(&mut self, card_id: CardInstanceId) -> Card {
-        let card_instance = pop_where(&mut self.cards, |card_instance| {
-            card_instance.id == card_id
-                && card_instance.card.get_card_data().energy_cost <= self.energy
-        })
-        .unwrap();
-        self.played.push(card_instance.clone());
-        self.energy -= card_instance.card.get_card_data().energy_cost;
-        card_instance.card
+    pub fn play(&mut self, card_id: CardInstanceId, modifiers: &PlayerModifiers) -> Card {
+        self.try_play(card_id, modifiers).unwrap()
     }
 
-    pub fn try_play(&mut self, card_id: CardInstanceId) -> Option<Card> {
+    pub fn try_play(
+        &mut self,
+        card_id: CardInstanceId,
+        modifiers: &PlayerModifiers,
+    ) -> Option<Card> {
         let card_instance = pop_where(&mut self.cards, |card_instance| {
-            card_instance.id == card_id
-                && card_instance.card.get_card_data().energy_cost <= self.energy
+            card_instance.id == card_id && modifiers.energy_cost(&card_instance.card) <= self.energy
         })?;
         self.played.push(card_instance.clone());
-        self.energy -= card_instance.card.get_card_data().energy_cost;
+        self.energy -= modifiers.energy_cost(&card_instance.card);
         Some(card_instance.card)
     }
 }
@@ -86,7 +86,7 @@ impl HitNumbers {
             };
 
             draw_text_with_origin(
-                format!("{}", physical_hit_number.number).as_str(),
+                format!("{:+}", physical_hit_number.number).as_str(),
                 physical_hit_number.pos.x,
                 physical_hit_number.pos.y,
                 28.0,
@@ -26,3 +26,7 @@ new_id_type!(BuildingLocationId);
 new_id_type!(PathId);
 new_id_type!(PlayerId);
 new_id_type!(GameId);
+
+/// Players on the same team are allies. Assigned by the level, so not random like the ids above
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TeamId(pub u32);
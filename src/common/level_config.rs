@@ -1,20 +1,55 @@
 use macroquad::{
-    color::{Color, BLUE, ORANGE},
+    color::{Color, BLUE, GREEN, ORANGE, PURPLE},
     math::Vec2,
 };
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
 
-use crate::world::{Direction, Zoning};
+use crate::{
+    ids::TeamId,
+    world::{Direction, Zoning},
+};
 
 pub struct LevelConfig {
     pub level_width: i32,
     pub level_height: i32,
     pub spawn_point_radius: f32,
     pub nearby_radius: f32,
-    pub player_configs: Vec<(Vec2, Direction, Color)>,
+    /// Base position, the direction its units walk the paths, color and team of each player
+    pub player_configs: Vec<(Vec2, Direction, Color, TeamId)>,
     pub building_locations: Vec<(Zoning, (f32, f32))>,
     pub paths: Vec<Vec<(f32, f32)>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Default, EnumIter, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum LevelPreset {
+    #[default]
+    Prototype,
+    /// Four bases, two against two
+    CrossroadsTeams,
+    /// Four bases, every player for themselves
+    CrossroadsFreeForAll,
+}
+
+impl LevelPreset {
+    pub fn iter() -> impl Iterator<Item = LevelPreset> {
+        <LevelPreset as IntoEnumIterator>::iter()
+    }
+
+    pub fn create(&self) -> LevelConfig {
+        match self {
+            LevelPreset::Prototype => get_prototype_level_config(),
+            LevelPreset::CrossroadsTeams => {
+                get_crossroads_level_config([TeamId(0), TeamId(0), TeamId(1), TeamId(1)])
+            }
+            LevelPreset::CrossroadsFreeForAll => {
+                get_crossroads_level_config([TeamId(0), TeamId(1), TeamId(2), TeamId(3)])
+            }
+        }
+    }
+}
+
 pub fn get_prototype_level_config() -> LevelConfig {
     LevelConfig {
         level_width: 2048,
@@ -22,7 +57,12 @@ pub fn get_prototype_level_config() -> LevelConfig {
         spawn_point_radius: 256.0,
         nearby_radius: 256.0,
         player_configs: vec![
-            (Vec2 { x: 152.0, y: 236.0 }, Direction::Positive, ORANGE),
+            (
+                Vec2 { x: 152.0, y: 236.0 },
+                Direction::Positive,
+                ORANGE,
+                TeamId(0),
+            ),
             (
                 Vec2 {
                     x: 1817.0,
@@ -30,6 +70,7 @@ pub fn get_prototype_level_config() -> LevelConfig {
                 },
                 Direction::Negative,
                 BLUE,
+                TeamId(1),
             ),
         ],
         building_locations: vec![
@@ -54,24 +95,27 @@ pub fn get_prototype_level_config() -> LevelConfig {
             (Zoning::Normal, (193.0, 966.0)),
             (Zoning::Normal, (1505.0, 1051.0)),
         ],
+        // Paths are oriented from the orange base to the blue base, and meet at junctions
         paths: vec![
+            // Trunk from the orange base
             vec![
                 (243.0, 276.0),
                 (420.0, 341.0),
                 (582.0, 331.0),
                 (679.0, 256.0),
                 (750.0, 257.0),
+            ],
+            // North lane
+            vec![
+                (750.0, 257.0),
                 (1015.0, 173.0),
                 (1457.0, 246.0),
                 (1700.0, 254.0),
                 (1817.0, 381.0),
                 (1854.0, 838.0),
             ],
+            // South lane
             vec![
-                (243.0, 276.0),
-                (420.0, 341.0),
-                (582.0, 331.0),
-                (679.0, 256.0),
                 (750.0, 257.0),
                 (969.0, 272.0),
                 (1086.0, 421.0),
@@ -81,8 +125,8 @@ pub fn get_prototype_level_config() -> LevelConfig {
                 (1441.0, 782.0),
                 (1458.0, 861.0),
                 (1487.0, 949.0),
-                (1661.0, 933.0),
             ],
+            // West lane
             vec![
                 (109.0, 352.0),
                 (122.0, 687.0),
@@ -95,8 +139,87 @@ pub fn get_prototype_level_config() -> LevelConfig {
                 (1173.0, 866.0),
                 (1255.0, 948.0),
                 (1487.0, 949.0),
-                (1661.0, 933.0),
             ],
+            // Tail to the blue base
+            vec![(1487.0, 949.0), (1661.0, 933.0)],
+        ],
+    }
+}
+
+/// Bases in the four corners. Lanes run from the western bases to the eastern ones, through the
+/// center and around the edges, so eastern units walk them backwards. Units reach the other base
+/// on their own side by turning around in the center. Every base has a short entry path, so the
+/// lane is picked where it splits
+fn get_crossroads_level_config(teams: [TeamId; 4]) -> LevelConfig {
+    let [north_west, south_west, north_east, south_east] = [
+        (300.0, 260.0),
+        (300.0, 892.0),
+        (1748.0, 260.0),
+        (1748.0, 892.0),
+    ];
+    let center = (1024.0, 576.0);
+    LevelConfig {
+        level_width: 2048,
+        level_height: 1152,
+        spawn_point_radius: 256.0,
+        nearby_radius: 256.0,
+        player_configs: vec![
+            (
+                Vec2 { x: 180.0, y: 180.0 },
+                Direction::Positive,
+                ORANGE,
+                teams[0],
+            ),
+            (
+                Vec2 { x: 180.0, y: 972.0 },
+                Direction::Positive,
+                GREEN,
+                teams[1],
+            ),
+            (
+                Vec2 {
+                    x: 1868.0,
+                    y: 180.0,
+                },
+                Direction::Negative,
+                BLUE,
+                teams[2],
+            ),
+            (
+                Vec2 {
+                    x: 1868.0,
+                    y: 972.0,
+                },
+                Direction::Negative,
+                PURPLE,
+                teams[3],
+            ),
+        ],
+        building_locations: vec![
+            (Zoning::Commerce, (160.0, 420.0)),
+            (Zoning::Commerce, (160.0, 732.0)),
+            (Zoning::Commerce, (1888.0, 420.0)),
+            (Zoning::Commerce, (1888.0, 732.0)),
+            (Zoning::Normal, (600.0, 260.0)),
+            (Zoning::Normal, (600.0, 892.0)),
+            (Zoning::Normal, (1448.0, 260.0)),
+            (Zoning::Normal, (1448.0, 892.0)),
+            (Zoning::Normal, (820.0, 576.0)),
+            (Zoning::Normal, (1228.0, 576.0)),
+            (Zoning::Normal, (1024.0, 400.0)),
+            (Zoning::Normal, (1024.0, 752.0)),
+        ],
+        paths: vec![
+            vec![(250.0, 230.0), north_west],
+            vec![(250.0, 922.0), south_west],
+            vec![north_east, (1798.0, 230.0)],
+            vec![south_east, (1798.0, 922.0)],
+            vec![north_west, (600.0, 380.0), center],
+            vec![south_west, (600.0, 772.0), center],
+            vec![center, (1448.0, 380.0), north_east],
+            vec![center, (1448.0, 772.0), south_east],
+            vec![north_west, (1024.0, 120.0), north_east],
+            vec![south_west, (1024.0, 1032.0), south_east],
         ],
     }
 }
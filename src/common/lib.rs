@@ -2,9 +2,15 @@ pub mod buff;
 pub mod card;
 pub mod component_attack;
 pub mod component_buff_source;
+pub mod component_construction;
 pub mod component_health;
 pub mod component_movement;
-pub mod component_spy;
+pub mod component_projectile;
+pub mod component_spawner;
+pub mod component_status_effects;
+pub mod component_stealth;
+pub mod component_trap;
+pub mod component_trigger;
 pub mod config;
 pub mod debug_draw_config;
 pub mod draw;
@@ -17,7 +23,6 @@ pub mod enum_flags;
 pub mod find_target;
 pub mod game_loop;
 pub mod game_state;
-pub mod gameplay_config;
 pub mod get_unit_spawnpoints;
 pub mod hand;
 pub mod hit_numbers;
@@ -25,8 +30,12 @@ pub mod ids;
 pub mod level_config;
 pub mod message_acknowledgement;
 pub mod network;
+pub mod network_simulation;
+pub mod path_graph;
 pub mod play_target;
+pub mod player_modifier;
 pub mod rect_transform;
+pub mod ruleset;
 pub mod serde_defs;
 pub mod server_player;
 pub mod sprite_id;
@@ -1,13 +1,15 @@
+use crate::network_simulation::{NetworkConditions, SimulatedUdpSocket};
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::Debug,
     marker::PhantomData,
     net::{SocketAddr, UdpSocket},
+    rc::Rc,
     time::SystemTime,
 };
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AckId(pub u64);
 impl Default for AckId {
     fn default() -> Self {
@@ -22,15 +24,23 @@ enum Message<MessageContent> {
     NoAck(MessageContent),
 }
 
+/// An already serialized message, shared between all recipients of a broadcast
+struct OutgoingMessage {
+    ack_id: Option<AckId>,
+    buf: Rc<[u8]>,
+    addr: SocketAddr,
+    last_sent_time: Option<SystemTime>,
+}
+
 pub struct AckUdpSocket<TxMessageContent, RxMessageContent>
 where
     TxMessageContent: Serialize + for<'de> Deserialize<'de>,
     RxMessageContent: Serialize + for<'de> Deserialize<'de>,
 {
-    udp_socket: UdpSocket,
+    udp_socket: SimulatedUdpSocket,
     resend_interval: std::time::Duration,
-    messages: Vec<(Message<TxMessageContent>, SocketAddr, Option<SystemTime>)>,
-    marker: PhantomData<RxMessageContent>,
+    messages: Vec<OutgoingMessage>,
+    marker: PhantomData<(TxMessageContent, RxMessageContent)>,
 }
 
 impl<TxMessageContent, RxMessageContent> AckUdpSocket<TxMessageContent, RxMessageContent>
@@ -40,23 +50,37 @@ where
 {
     pub fn new(udp_socket: UdpSocket, resend_interval: std::time::Duration) -> Self {
         Self {
-            udp_socket,
+            udp_socket: SimulatedUdpSocket::new(udp_socket),
             resend_interval,
             messages: Vec::new(),
             marker: PhantomData,
         }
     }
 
+    pub fn simulate_network_conditions(&mut self, conditions: Option<NetworkConditions>) {
+        self.udp_socket.set_conditions(conditions);
+    }
+
     pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
         self.udp_socket.local_addr()
     }
 
-    pub fn queue(&mut self, message_content: TxMessageContent, addr: &SocketAddr, needs_ack: bool) {
-        let message = match needs_ack {
-            true => Message::Ack((AckId::default(), message_content)),
-            false => Message::NoAck(message_content),
+    fn serialize(message_content: &TxMessageContent, ack_id: Option<AckId>) -> Rc<[u8]> {
+        let buf = match ack_id {
+            Some(ack_id) => rmp_serde::to_vec(&Message::Ack((ack_id, message_content))),
+            None => rmp_serde::to_vec(&Message::NoAck(message_content)),
         };
-        self.messages.push((message, *addr, None));
+        buf.unwrap().into()
+    }
+
+    pub fn queue(&mut self, message_content: TxMessageContent, addr: &SocketAddr, needs_ack: bool) {
+        let ack_id = needs_ack.then(AckId::default);
+        self.messages.push(OutgoingMessage {
+            ack_id,
+            buf: Self::serialize(&message_content, ack_id),
+            addr: *addr,
+            last_sent_time: None,
+        });
     }
 
     pub fn send_to(
@@ -65,30 +89,46 @@ where
         addr: &SocketAddr,
         needs_ack: bool,
     ) {
-        let message = match needs_ack {
-            true => Message::Ack((AckId::default(), message_content)),
-            false => Message::NoAck(message_content),
-        };
-        Self::send_single(&self.udp_socket, &message, addr);
-        if needs_ack {
-            self.messages
-                .push((message, *addr, Some(SystemTime::now())));
+        self.broadcast(&message_content, [addr], needs_ack);
+    }
+
+    /// Serializes the message once and sends the same bytes to every address
+    pub fn broadcast<'a>(
+        &mut self,
+        message_content: &TxMessageContent,
+        addrs: impl IntoIterator<Item = &'a SocketAddr>,
+        needs_ack: bool,
+    ) {
+        let ack_id = needs_ack.then(AckId::default);
+        let buf = Self::serialize(message_content, ack_id);
+        for addr in addrs {
+            Self::send_single(&mut self.udp_socket, &buf, addr);
+            if needs_ack {
+                self.messages.push(OutgoingMessage {
+                    ack_id,
+                    buf: buf.clone(),
+                    addr: *addr,
+                    last_sent_time: Some(SystemTime::now()),
+                });
+            }
         }
     }
 
-    fn send_single(udp_socket: &UdpSocket, message: &Message<TxMessageContent>, addr: &SocketAddr) {
-        let buf = rmp_serde::to_vec(&message).unwrap();
-        udp_socket.send_to(buf.as_slice(), *addr).unwrap();
+    fn send_single(udp_socket: &mut SimulatedUdpSocket, buf: &[u8], addr: &SocketAddr) {
+        udp_socket.send_to(buf, *addr).unwrap();
     }
 
     pub fn send_queued(&mut self) {
-        self.messages.retain_mut(|(message, addr, last_sent_time)| {
-            if last_sent_time.is_some_and(|time| time.elapsed().unwrap() < self.resend_interval) {
+        self.messages.retain_mut(|message| {
+            if message
+                .last_sent_time
+                .is_some_and(|time| time.elapsed().unwrap() < self.resend_interval)
+            {
                 return true;
             }
-            *last_sent_time = Some(SystemTime::now());
-            Self::send_single(&self.udp_socket, message, addr);
-            matches!(message, Message::Ack(_))
+            message.last_sent_time = Some(SystemTime::now());
+            Self::send_single(&mut self.udp_socket, &message.buf, &message.addr);
+            message.ack_id.is_some()
         });
     }
 
@@ -118,20 +158,21 @@ where
         let message = match deserialization_result {
             Ok(message) => message,
             Err(err) => {
-                dbg!(err);
-                debug_assert!(false);
+                // Expected from peers running another protocol version, which the handshake rejects
+                println!("Dropping undecodable message from {}: {}", addr, err);
                 return None;
             }
         };
         match message {
             Message::AckReply(ack_id) => {
-                self.messages.retain_mut(
-                    |(message, _, _)| matches!(message, Message::Ack((id, _)) if *id != ack_id),
-                );
+                self.messages
+                    .retain(|message| message.ack_id != Some(ack_id) || message.addr != addr);
                 self.receive()
             }
             Message::Ack((ack_id, content)) => {
-                Self::send_single(&self.udp_socket, &Message::AckReply(ack_id), &addr);
+                let buf =
+                    rmp_serde::to_vec(&Message::<RxMessageContent>::AckReply(ack_id)).unwrap();
+                Self::send_single(&mut self.udp_socket, &buf, &addr);
                 Some((content, addr))
             }
             Message::NoAck(content) => Some((content, addr)),
@@ -1,10 +1,11 @@
 use crate::{
     card::Card,
+    entity_blueprint::EntityBlueprint,
     game_state::{
         DynamicGameState, GameMetadata, SemiStaticGameState, ServerControlledGameState,
         StaticGameState,
     },
-    ids::{CardInstanceId, PlayerId},
+    ids::{CardInstanceId, EntityId, PlayerId},
     message_acknowledgement::AckUdpSocket,
     play_target::PlayTarget,
 };
@@ -21,14 +22,79 @@ pub fn hash_client_addr(addr: &SocketAddr) -> PlayerId {
     PlayerId(id)
 }
 
+/// Bump whenever the wire format of `ClientMessage` or `ServerMessage` changes.
+pub const PROTOCOL_VERSION: u32 = 7;
+
+/// Hash of everything both sides must agree on for `Card` and `EntityBlueprint` variants to mean
+/// the same thing: variant order, card data and the serialized blueprint entities.
+pub fn content_hash() -> u64 {
+    let mut bytes = Vec::new();
+    for card in Card::iter() {
+        let card_data = card.get_card_data();
+        bytes.extend(format!("{:?}", card).bytes());
+        bytes.extend(card_data.name.bytes());
+        bytes.extend(card_data.description.bytes());
+        bytes.extend(card_data.energy_cost.to_le_bytes());
+        bytes.extend(card_data.attack.unwrap_or(-1).to_le_bytes());
+        bytes.extend(card_data.health.unwrap_or(-1).to_le_bytes());
+    }
+    for entity_blueprint in EntityBlueprint::iter() {
+        bytes.extend(format!("{:?}", entity_blueprint).bytes());
+        bytes.extend(rmp_serde::to_vec(&entity_blueprint.create()).unwrap());
+    }
+    // FNV-1a, as std's DefaultHasher is not guaranteed to be stable between compiler versions
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtocolHello {
+    pub protocol_version: u32,
+    pub content_hash: u64,
+}
+
+impl ProtocolHello {
+    pub fn current() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            content_hash: content_hash(),
+        }
+    }
+
+    pub fn check_compatible(&self, other: &ProtocolHello) -> Result<(), String> {
+        if self.protocol_version != other.protocol_version {
+            return Err(format!(
+                "Protocol version mismatch: server runs v{}, client runs v{}.\nPlease update so both use the same version.",
+                self.protocol_version, other.protocol_version
+            ));
+        }
+        if self.content_hash != other.content_hash {
+            return Err(format!(
+                "Card definitions differ: server has {:016x}, client has {:016x}.\nPlease update so both use the same version.",
+                self.content_hash, other.content_hash
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ClientMessage {
+    /// Must stay the first variant so that mismatched clients can still be understood
+    Hello(ProtocolHello),
     JoinGame(Vec<Card>),
+    SpectateGame,
     PlayCard(CardInstanceId, PlayTarget),
+    SellBuilding(EntityId),
+    UpgradeBuilding(EntityId),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ServerMessageData {
+    /// Must stay the first variants so that mismatched clients can still be answered
+    HelloAccepted,
+    HelloRejected(String),
     StaticGameState(StaticGameState),
     SemiStaticGameState(SemiStaticGameState),
     DynamicGameState(DynamicGameState),
@@ -0,0 +1,136 @@
+use itertools::Itertools;
+use rand::{thread_rng, Rng};
+use std::{
+    net::{SocketAddr, UdpSocket},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+pub const SIMULATE_NETWORK_ARG: &str = "--simulate-network";
+
+/// Artificial conditions applied to outgoing datagrams. Pass the flag to both client and server to
+/// degrade both directions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkConditions {
+    pub latency: Duration,
+    pub jitter: Duration,
+    pub loss: f32,
+    pub duplication: f32,
+    pub reordering: f32,
+}
+
+impl NetworkConditions {
+    pub fn from_args() -> Option<Self> {
+        let args = std::env::args().collect_vec();
+        let value = args
+            .iter()
+            .position(|arg| arg == SIMULATE_NETWORK_ARG)
+            .and_then(|idx| args.get(idx + 1))?;
+        Some(Self::from_str(value).unwrap_or_else(|err| panic!("{}", err)))
+    }
+
+    fn sample_delay(&self) -> Duration {
+        let jitter = self.jitter.mul_f32(thread_rng().gen_range(0.0..=1.0));
+        self.latency + jitter
+    }
+}
+
+/// Parses e.g. `latency=150,jitter=50,loss=0.1,duplication=0.02,reordering=0.05`, where latency
+/// and jitter are in milliseconds and the rest are probabilities. Omitted keys default to zero.
+impl FromStr for NetworkConditions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let from_millis = |value: f32| Duration::from_micros((value * 1000.0).round() as u64);
+        let mut conditions = NetworkConditions::default();
+        for pair in s.split(',').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", pair))?;
+            let value = f32::from_str(value)
+                .map_err(|_| format!("Expected a number for '{}', got '{}'", key, value))?;
+            match key {
+                "latency" => conditions.latency = from_millis(value),
+                "jitter" => conditions.jitter = from_millis(value),
+                "loss" => conditions.loss = value,
+                "duplication" => conditions.duplication = value,
+                "reordering" => conditions.reordering = value,
+                _ => {
+                    return Err(format!(
+                        "Unknown network condition '{}', expected one of latency, jitter, loss, duplication, reordering",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(conditions)
+    }
+}
+
+/// Thin layer over `UdpSocket` that holds back outgoing datagrams according to `NetworkConditions`.
+/// Delayed datagrams are flushed whenever the socket is used.
+pub struct SimulatedUdpSocket {
+    udp_socket: UdpSocket,
+    conditions: Option<NetworkConditions>,
+    in_flight: Vec<(SystemTime, Vec<u8>, SocketAddr)>,
+}
+
+impl SimulatedUdpSocket {
+    pub fn new(udp_socket: UdpSocket) -> Self {
+        Self {
+            udp_socket,
+            conditions: None,
+            in_flight: Vec::new(),
+        }
+    }
+
+    pub fn set_conditions(&mut self, conditions: Option<NetworkConditions>) {
+        self.conditions = conditions;
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.udp_socket.local_addr()
+    }
+
+    pub fn send_to(&mut self, buf: &[u8], addr: SocketAddr) -> std::io::Result<usize> {
+        let Some(conditions) = &self.conditions else {
+            return self.udp_socket.send_to(buf, addr);
+        };
+        let mut rng = thread_rng();
+        if rng.gen::<f32>() < conditions.loss {
+            return Ok(buf.len());
+        }
+        let copies = if rng.gen::<f32>() < conditions.duplication {
+            2
+        } else {
+            1
+        };
+        for _ in 0..copies {
+            let mut delay = conditions.sample_delay();
+            if rng.gen::<f32>() < conditions.reordering {
+                // Held back long enough to arrive after datagrams sent later
+                delay += conditions.latency + conditions.jitter + Duration::from_millis(50);
+            }
+            self.in_flight
+                .push((SystemTime::now() + delay, buf.to_vec(), addr));
+        }
+        self.flush();
+        Ok(buf.len())
+    }
+
+    pub fn recv_from(&mut self, buf: &mut [u8]) -> std::io::Result<(usize, SocketAddr)> {
+        self.flush();
+        self.udp_socket.recv_from(buf)
+    }
+
+    fn flush(&mut self) {
+        let now = SystemTime::now();
+        self.in_flight.retain(|(due, buf, addr)| {
+            if *due > now {
+                return true;
+            }
+            let _ = self.udp_socket.send_to(buf, *addr);
+            false
+        });
+    }
+}
@@ -0,0 +1,366 @@
+//! Paths form a graph. Every path is oriented from the positive player's side to the negative
+//! player's side, and a junction is a node where the end of one path meets the start of others.
+//! With more than two bases, units may also turn onto a path ending at their junction and walk it
+//! backwards, e.g. to reach an enemy on their own side of the map.
+
+use crate::{
+    config::DEFAULT_UNIT_DETECTION_RADIUS,
+    entity::EntityTag,
+    game_state::{DynamicGameState, StaticGameState},
+    ids::{PathId, PlayerId},
+    serde_defs::Vec2Def,
+    server_player::are_allies,
+    world::{next_path_idx, Direction},
+};
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Nodes closer than this are the same junction
+const JUNCTION_RADIUS: f32 = 1.0;
+
+/// How a unit picks the next path at a junction
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum BranchPolicy {
+    /// Takes this path where it branches off, and the shortest route elsewhere
+    FixedLane(PathId),
+    #[default]
+    ShortestToEnemyBase,
+    /// Takes the path passing the fewest enemy towers
+    LeastDefended,
+    /// Takes the shortest route to the position, used by builders
+    Towards(#[serde(with = "Vec2Def")] Vec2),
+}
+
+/// A node this close to a base at the end of the paths leads into it
+const BASE_REACH_RADIUS: f32 = 256.0;
+
+enum RouteGoal {
+    /// Where the paths end walking forwards
+    GraphEnd,
+    Near(Vec2, f32),
+    /// A dead end leading into one of the bases
+    BaseAt(Vec<Vec2>),
+}
+
+fn node_pos((x, y): (f32, f32)) -> Vec2 {
+    Vec2::new(x, y)
+}
+
+/// Index of the last node of the path when walked in `direction`
+pub fn path_end_idx(path: &[(f32, f32)], direction: &Direction) -> usize {
+    match direction {
+        Direction::Positive => path.len() - 1,
+        Direction::Negative => 0,
+    }
+}
+
+/// The paths continuing from the end of `path_id` when walked in `direction`
+pub fn get_branches(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    direction: &Direction,
+) -> Vec<PathId> {
+    let path = static_game_state.paths.get(&path_id).unwrap();
+    let junction_pos = node_pos(path[path_end_idx(path, direction)]);
+    let mut branches: Vec<PathId> = static_game_state
+        .paths
+        .iter()
+        .filter(|(other_id, other)| {
+            **other_id != path_id
+                && node_pos(other[path_end_idx(other, &direction.flipped())]).distance(junction_pos)
+                    < JUNCTION_RADIUS
+        })
+        .map(|(other_id, _)| *other_id)
+        .collect();
+    branches.sort_by_key(|branch| branch.0);
+    branches
+}
+
+/// The paths a unit can continue on from the end of `path_id` when walked in `direction`: the
+/// branches, and the other paths ending at the junction walked backwards
+pub fn get_junction_exits(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    direction: &Direction,
+) -> Vec<(PathId, Direction)> {
+    let path = static_game_state.paths.get(&path_id).unwrap();
+    let junction_pos = node_pos(path[path_end_idx(path, direction)]);
+    let mut exits: Vec<(PathId, Direction)> = static_game_state
+        .paths
+        .iter()
+        .filter(|(other_id, _)| **other_id != path_id)
+        .flat_map(|(other_id, other)| {
+            [direction.clone(), direction.flipped()]
+                .into_iter()
+                .filter(|exit_direction| {
+                    node_pos(other[path_end_idx(other, &exit_direction.flipped())])
+                        .distance(junction_pos)
+                        < JUNCTION_RADIUS
+                })
+                .map(|exit_direction| (*other_id, exit_direction))
+        })
+        .collect();
+    exits.sort_by_key(|(exit, exit_direction)| (exit.0, *exit_direction != *direction));
+    exits
+}
+
+/// Whether the paths are the same or meet at a junction
+pub fn paths_are_connected(static_game_state: &StaticGameState, a: PathId, b: PathId) -> bool {
+    if a == b {
+        return true;
+    }
+    let endpoints = |path_id: PathId| {
+        let path = static_game_state.paths.get(&path_id).unwrap();
+        [path[0], path[path.len() - 1]].map(node_pos)
+    };
+    let endpoints_b = endpoints(b);
+    endpoints(a).iter().any(|endpoint_a| {
+        endpoints_b
+            .iter()
+            .any(|endpoint_b| endpoint_a.distance(*endpoint_b) < JUNCTION_RADIUS)
+    })
+}
+
+/// The lanes a unit walking from `path_id` can be sent down, at the first junction with a choice
+pub fn get_lane_choices(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    direction: &Direction,
+) -> Vec<PathId> {
+    let mut path_id = path_id;
+    for _ in 0..static_game_state.paths.len() {
+        let branches = get_branches(static_game_state, path_id, direction);
+        match branches.as_slice() {
+            [] => return Vec::new(),
+            [branch] => path_id = *branch,
+            _ => return branches,
+        }
+    }
+    Vec::new()
+}
+
+fn shortest_route(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    path_idx: usize,
+    direction: &Direction,
+    goal: &RouteGoal,
+    visited: &mut Vec<PathId>,
+) -> Option<f32> {
+    let path = static_game_state.paths.get(&path_id).unwrap();
+    let end_idx = path_end_idx(path, direction);
+    let mut path_idx = path_idx.min(path.len() - 1);
+    let mut length = 0.0;
+    loop {
+        if let RouteGoal::Near(pos, range) = goal {
+            if node_pos(path[path_idx]).distance(*pos) < *range {
+                return Some(length);
+            }
+        }
+        if path_idx == end_idx {
+            break;
+        }
+        let next_idx = next_path_idx(path_idx, direction.clone());
+        length += node_pos(path[path_idx]).distance(node_pos(path[next_idx]));
+        path_idx = next_idx;
+    }
+    let exits = match goal {
+        RouteGoal::GraphEnd => get_branches(static_game_state, path_id, direction)
+            .into_iter()
+            .map(|branch| (branch, direction.clone()))
+            .collect(),
+        RouteGoal::Near(..) | RouteGoal::BaseAt(_) => {
+            get_junction_exits(static_game_state, path_id, direction)
+        }
+    };
+    if exits.is_empty() {
+        return match goal {
+            RouteGoal::GraphEnd => Some(length),
+            RouteGoal::Near(..) => None,
+            RouteGoal::BaseAt(base_positions) => base_positions
+                .iter()
+                .any(|base_pos| node_pos(path[end_idx]).distance(*base_pos) < BASE_REACH_RADIUS)
+                .then_some(length),
+        };
+    }
+    visited.push(path_id);
+    let route = exits
+        .into_iter()
+        .filter(|(exit, _)| !visited.contains(exit))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .filter_map(|(exit, exit_direction)| {
+            let exit_path = static_game_state.paths.get(&exit).unwrap();
+            shortest_route(
+                static_game_state,
+                exit,
+                path_end_idx(exit_path, &exit_direction.flipped()),
+                &exit_direction,
+                goal,
+                visited,
+            )
+        })
+        .min_by(|a, b| a.total_cmp(b))
+        .map(|exit_length| length + exit_length);
+    visited.pop();
+    route
+}
+
+/// Shortest distance along the graph from the node to where the paths end, i.e. an enemy base
+pub fn distance_to_graph_end(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    path_idx: usize,
+    direction: &Direction,
+) -> f32 {
+    shortest_route(
+        static_game_state,
+        path_id,
+        path_idx,
+        direction,
+        &RouteGoal::GraphEnd,
+        &mut Vec::new(),
+    )
+    .unwrap_or(0.0)
+}
+
+/// Shortest distance along the graph from the node to a node within `range` of `pos`
+pub fn distance_along_graph_to(
+    static_game_state: &StaticGameState,
+    path_id: PathId,
+    path_idx: usize,
+    direction: &Direction,
+    pos: Vec2,
+    range: f32,
+) -> Option<f32> {
+    shortest_route(
+        static_game_state,
+        path_id,
+        path_idx,
+        direction,
+        &RouteGoal::Near(pos, range),
+        &mut Vec::new(),
+    )
+}
+
+fn count_enemy_towers_along(
+    static_game_state: &StaticGameState,
+    dynamic_game_state: &DynamicGameState,
+    path_id: PathId,
+    owner: PlayerId,
+) -> usize {
+    let path = static_game_state.paths.get(&path_id).unwrap();
+    dynamic_game_state
+        .entities
+        .iter()
+        .filter(|entity_instance| {
+            !are_allies(&dynamic_game_state.players, owner, entity_instance.owner)
+                && entity_instance.entity.tag == EntityTag::Tower
+                && path.iter().any(|node| {
+                    node_pos(*node).distance(entity_instance.pos) < DEFAULT_UNIT_DETECTION_RADIUS
+                })
+        })
+        .count()
+}
+
+/// The path and direction to continue on from the end of `path_id`, or `None` at the end of the
+/// graph. Only paths leading to an enemy base are considered, unless there are none
+pub fn choose_branch(
+    static_game_state: &StaticGameState,
+    dynamic_game_state: &DynamicGameState,
+    owner: PlayerId,
+    path_id: PathId,
+    direction: &Direction,
+    branch_policy: &BranchPolicy,
+) -> Option<(PathId, Direction)> {
+    let enemy_base_positions: Vec<Vec2> = dynamic_game_state
+        .entities
+        .iter()
+        .filter(|entity_instance| {
+            entity_instance.entity.tag == EntityTag::Base
+                && !are_allies(&dynamic_game_state.players, owner, entity_instance.owner)
+        })
+        .map(|entity_instance| entity_instance.pos)
+        .collect();
+    let exit_start_idx = |(exit, exit_direction): &(PathId, Direction)| {
+        path_end_idx(
+            static_game_state.paths.get(exit).unwrap(),
+            &exit_direction.flipped(),
+        )
+    };
+    let route_length = |exit: &(PathId, Direction), goal: &RouteGoal| {
+        shortest_route(
+            static_game_state,
+            exit.0,
+            exit_start_idx(exit),
+            &exit.1,
+            goal,
+            &mut vec![path_id],
+        )
+    };
+    let mut goal = RouteGoal::BaseAt(enemy_base_positions);
+    let mut exits: Vec<(PathId, Direction)> =
+        get_junction_exits(static_game_state, path_id, direction)
+            .into_iter()
+            .filter(|exit| route_length(exit, &goal).is_some())
+            .collect();
+    if exits.is_empty() {
+        // No enemy base in reach, so head on to the end of the graph
+        goal = RouteGoal::GraphEnd;
+        exits = get_branches(static_game_state, path_id, direction)
+            .into_iter()
+            .map(|branch| (branch, direction.clone()))
+            .collect();
+    }
+    let shortest = |exits: Vec<(PathId, Direction)>| {
+        exits
+            .into_iter()
+            .filter_map(|exit| route_length(&exit, &goal).map(|length| (exit, length)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(exit, _)| exit)
+    };
+    match branch_policy {
+        BranchPolicy::FixedLane(lane) if exits.iter().any(|(exit, _)| exit == lane) => {
+            exits.into_iter().find(|(exit, _)| exit == lane)
+        }
+        BranchPolicy::FixedLane(_) | BranchPolicy::ShortestToEnemyBase => shortest(exits),
+        BranchPolicy::LeastDefended => {
+            let tower_counts: Vec<((PathId, Direction), usize)> = exits
+                .into_iter()
+                .map(|exit| {
+                    let tower_count = count_enemy_towers_along(
+                        static_game_state,
+                        dynamic_game_state,
+                        exit.0,
+                        owner,
+                    );
+                    (exit, tower_count)
+                })
+                .collect();
+            let fewest = tower_counts.iter().map(|(_, count)| *count).min()?;
+            shortest(
+                tower_counts
+                    .into_iter()
+                    .filter(|(_, count)| *count == fewest)
+                    .map(|(exit, _)| exit)
+                    .collect(),
+            )
+        }
+        BranchPolicy::Towards(pos) => exits
+            .iter()
+            .filter_map(|exit| {
+                distance_along_graph_to(
+                    static_game_state,
+                    exit.0,
+                    exit_start_idx(exit),
+                    &exit.1,
+                    *pos,
+                    DEFAULT_UNIT_DETECTION_RADIUS,
+                )
+                .map(|distance| (exit.clone(), distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(exit, _)| exit)
+            .or_else(|| shortest(exits)),
+    }
+}
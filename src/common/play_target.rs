@@ -1,6 +1,7 @@
 use crate::{
     game_state::{DynamicGameState, SemiStaticGameState, StaticGameState},
     ids::{BuildingLocationId, EntityId, PathId, PlayerId},
+    path_graph::BranchPolicy,
     rect_transform::RectTransform,
     world::{get_path_pos, Direction},
 };
@@ -23,6 +24,7 @@ pub struct UnitSpawnpointTarget {
     pub path_id: PathId,
     pub path_idx: usize,
     pub direction: Direction,
+    pub branch_policy: BranchPolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +32,12 @@ pub struct BuildingLocationTarget {
     pub id: BuildingLocationId,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PathNodeTarget {
+    pub path_id: PathId,
+    pub path_idx: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityTarget {
     pub id: EntityId,
@@ -40,6 +48,7 @@ pub enum PlayTarget {
     WorldPos(WorldPosTarget),
     UnitSpawnpoint(UnitSpawnpointTarget),
     BuildingLocation(BuildingLocationTarget),
+    PathNode(PathNodeTarget),
     Entity(EntityTarget),
 }
 
@@ -96,6 +105,7 @@ macro_rules! try_from_play_args {
 try_from_play_args!(WorldPosTarget, WorldPos);
 try_from_play_args!(UnitSpawnpointTarget, UnitSpawnpoint);
 try_from_play_args!(BuildingLocationTarget, BuildingLocation);
+try_from_play_args!(PathNodeTarget, PathNode);
 try_from_play_args!(EntityTarget, Entity);
 
 pub struct SpecificPlayFn<T> {
@@ -124,10 +134,9 @@ impl<T> SpecificPlayFn<T> {
         }
     }
     pub fn exec(&self, args: PlayArgs<T>) -> bool {
-        match self.target_is_invalid {
-            Some(f) => f(args.to_target_is_invalid_args()),
-            None => false,
-        };
+        if self.target_is_invalid(args.to_target_is_invalid_args()) {
+            return false;
+        }
         (self.play)(args)
     }
 }
@@ -136,6 +145,7 @@ pub enum PlayFn {
     WorldPos(SpecificPlayFn<WorldPosTarget>),
     UnitSpawnPoint(SpecificPlayFn<UnitSpawnpointTarget>),
     BuildingLocation(SpecificPlayFn<BuildingLocationTarget>),
+    PathNode(SpecificPlayFn<PathNodeTarget>),
     Entity(SpecificPlayFn<EntityTarget>),
 }
 
@@ -161,6 +171,9 @@ impl PlayFn {
             PlayFn::BuildingLocation(specific_play_fn) => {
                 Ok(specific_play_fn.exec(PlayArgs::try_from(play_args)?))
             }
+            PlayFn::PathNode(specific_play_fn) => {
+                Ok(specific_play_fn.exec(PlayArgs::try_from(play_args)?))
+            }
             PlayFn::Entity(specific_play_fn) => {
                 Ok(specific_play_fn.exec(PlayArgs::try_from(play_args)?))
             }
@@ -176,6 +189,7 @@ pub fn unit_spawnpoint_target_transform(
         path_id,
         path_idx,
         direction: _,
+        branch_policy: _,
     } = target;
 
     let Vec2 { x, y } = get_path_pos(static_game_state, *path_id, *path_idx);
@@ -188,3 +202,22 @@ pub fn unit_spawnpoint_target_transform(
         ..Default::default()
     }
 }
+
+/// Every path node, with nodes shared between paths listed once
+pub fn get_path_node_targets(static_game_state: &StaticGameState) -> Vec<PathNodeTarget> {
+    let mut path_ids = static_game_state.paths.keys().copied().collect::<Vec<_>>();
+    path_ids.sort_by_key(|path_id| path_id.0);
+    let mut targets: Vec<PathNodeTarget> = Vec::new();
+    for path_id in path_ids {
+        let path = static_game_state.paths.get(&path_id).unwrap();
+        for path_idx in 0..path.len() {
+            let pos = get_path_pos(static_game_state, path_id, path_idx);
+            if !targets.iter().any(|target| {
+                get_path_pos(static_game_state, target.path_id, target.path_idx) == pos
+            }) {
+                targets.push(PathNodeTarget { path_id, path_idx });
+            }
+        }
+    }
+    targets
+}
@@ -0,0 +1,169 @@
+use crate::{
+    buff::{apply_arithmetic_buffs, ArithmeticBuff},
+    card::{Card, CardTag},
+    game_state::DynamicGameState,
+    ruleset::Ruleset,
+};
+use serde::{Deserialize, Serialize};
+
+/// Changes the economy of the owning player, while the entity carrying it stands or the card
+/// effect lasts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PlayerModifier {
+    DrawSpeed(ArithmeticBuff),
+    EnergyGeneration(ArithmeticBuff),
+    MaxEnergy(i32),
+    MaxHandSize(i32),
+    /// Added to the energy cost of cards with the tag
+    CardCost(CardTag, i32),
+    /// Extra energy every time a card is drawn, which is when a new round of the draw timer starts
+    DrawBonusEnergy(i32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedPlayerModifier {
+    pub modifier: PlayerModifier,
+    pub seconds_left: f32,
+}
+
+impl TimedPlayerModifier {
+    pub fn new(modifier: PlayerModifier, seconds: f32) -> Self {
+        Self {
+            modifier,
+            seconds_left: seconds,
+        }
+    }
+}
+
+/// All modifiers currently affecting a player
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerModifiers {
+    pub modifiers: Vec<PlayerModifier>,
+}
+
+impl PlayerModifiers {
+    pub fn card_draw_per_second(&self, ruleset: &Ruleset) -> f32 {
+        let buffs: Vec<ArithmeticBuff> = self
+            .modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::DrawSpeed(buff) => Some(buff.clone()),
+                _ => None,
+            })
+            .collect();
+        apply_arithmetic_buffs(ruleset.card_draw_per_second, &buffs)
+    }
+
+    pub fn energy_per_second(&self, ruleset: &Ruleset) -> f32 {
+        let buffs: Vec<ArithmeticBuff> = self
+            .modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::EnergyGeneration(buff) => Some(buff.clone()),
+                _ => None,
+            })
+            .collect();
+        apply_arithmetic_buffs(ruleset.energy_per_second, &buffs)
+    }
+
+    pub fn max_energy(&self, ruleset: &Ruleset) -> i32 {
+        let extra: i32 = self
+            .modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::MaxEnergy(extra) => Some(*extra),
+                _ => None,
+            })
+            .sum();
+        (ruleset.max_energy + extra).max(0)
+    }
+
+    pub fn max_hand_size(&self, ruleset: &Ruleset) -> usize {
+        let extra: i32 = self
+            .modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::MaxHandSize(extra) => Some(*extra),
+                _ => None,
+            })
+            .sum();
+        (ruleset.max_hand_size as i32 + extra).max(0) as usize
+    }
+
+    /// Never below zero, so discounts can't pay out energy
+    pub fn energy_cost(&self, card: &Card) -> i32 {
+        let tag = card.get_tag();
+        let change: i32 = self
+            .modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::CardCost(cost_tag, change) if *cost_tag == tag => Some(*change),
+                _ => None,
+            })
+            .sum();
+        (card.get_card_data().energy_cost + change).max(0)
+    }
+
+    pub fn draw_bonus_energy(&self) -> i32 {
+        self.modifiers
+            .iter()
+            .filter_map(|modifier| match modifier {
+                PlayerModifier::DrawBonusEnergy(energy) => Some(*energy),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// One line per active modifier, for the player's overlay
+    pub fn describe(&self) -> Vec<String> {
+        let describe_buff = |name: &str, buff: &ArithmeticBuff| {
+            if buff.multiplier != 1.0 {
+                format!("{} {:+.0}%", name, (buff.multiplier - 1.0) * 100.0)
+            } else {
+                format!("{} {:+}", name, buff.additive_value)
+            }
+        };
+        self.modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PlayerModifier::DrawSpeed(buff) => describe_buff("Draw speed", buff),
+                PlayerModifier::EnergyGeneration(buff) => describe_buff("Energy rate", buff),
+                PlayerModifier::MaxEnergy(extra) => format!("Max energy {:+}", extra),
+                PlayerModifier::MaxHandSize(extra) => format!("Max hand size {:+}", extra),
+                PlayerModifier::CardCost(tag, change) => {
+                    format!("{:?} card cost {:+}", tag, change)
+                }
+                PlayerModifier::DrawBonusEnergy(energy) => {
+                    format!("Energy per card drawn {:+}", energy)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Counts down the timed modifiers of every player, and gathers the modifiers they have from
+/// their entities. Buildings don't count until they are constructed
+pub fn update_player_modifiers(dynamic_game_state: &mut DynamicGameState, dt: f32) {
+    for (player_id, player) in dynamic_game_state.players.iter_mut() {
+        for timed_modifier in player.timed_modifiers.iter_mut() {
+            timed_modifier.seconds_left -= dt;
+        }
+        player
+            .timed_modifiers
+            .retain(|timed_modifier| timed_modifier.seconds_left > 0.0);
+        player.modifiers.modifiers = dynamic_game_state
+            .entities
+            .iter()
+            .filter(|entity_instance| {
+                entity_instance.owner == *player_id && entity_instance.entity.construction.is_none()
+            })
+            .flat_map(|entity_instance| entity_instance.entity.player_modifiers.iter().cloned())
+            .chain(
+                player
+                    .timed_modifiers
+                    .iter()
+                    .map(|timed_modifier| timed_modifier.modifier.clone()),
+            )
+            .collect();
+    }
+}
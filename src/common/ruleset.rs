@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, EnumIter, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum RulesetPreset {
+    #[default]
+    Standard,
+    Fast,
+    Sandbox,
+}
+
+impl RulesetPreset {
+    pub fn iter() -> impl Iterator<Item = RulesetPreset> {
+        <RulesetPreset as IntoEnumIterator>::iter()
+    }
+}
+
+/// What a player gets back when their builder dies before the building is placed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FailedConstructionCompensation {
+    Nothing,
+    RefundEnergy,
+    ReturnCard,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub preset: RulesetPreset,
+    pub starting_hand_size: i32,
+    pub max_hand_size: usize,
+    pub starting_energy: i32,
+    pub max_energy: i32,
+    pub card_draw_per_second: f32,
+    pub energy_per_second: f32,
+    pub infinite_energy: bool,
+    pub failed_construction_compensation: FailedConstructionCompensation,
+    /// Fraction of the energy invested in a building that is refunded when selling it
+    pub sell_refund_fraction: f32,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        RulesetPreset::default().create()
+    }
+}
+
+impl RulesetPreset {
+    pub fn create(&self) -> Ruleset {
+        let base_seconds_to_draw_card = 20.0;
+        let base_seconds_to_get_energy = 7.0;
+        let standard = Ruleset {
+            preset: RulesetPreset::Standard,
+            starting_hand_size: 4,
+            max_hand_size: 10,
+            starting_energy: 0,
+            max_energy: 10,
+            card_draw_per_second: 1.0 / base_seconds_to_draw_card,
+            energy_per_second: 1.0 / base_seconds_to_get_energy,
+            infinite_energy: false,
+            failed_construction_compensation: FailedConstructionCompensation::RefundEnergy,
+            sell_refund_fraction: 0.5,
+        };
+        match self {
+            RulesetPreset::Standard => standard,
+            RulesetPreset::Fast => Ruleset {
+                preset: RulesetPreset::Fast,
+                starting_hand_size: 5,
+                starting_energy: 3,
+                card_draw_per_second: 2.0 * standard.card_draw_per_second,
+                energy_per_second: 2.0 * standard.energy_per_second,
+                ..standard
+            },
+            RulesetPreset::Sandbox => Ruleset {
+                preset: RulesetPreset::Sandbox,
+                starting_energy: standard.max_energy,
+                infinite_energy: true,
+                ..standard
+            },
+        }
+    }
+}
@@ -1,23 +1,47 @@
 use crate::card::Card;
+use crate::ids::{PlayerId, TeamId};
+use crate::player_modifier::{PlayerModifiers, TimedPlayerModifier};
 use crate::serde_defs::ColorDef;
 use crate::{hand::Hand, world::Direction};
 use macroquad::color::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerPlayer {
     pub direction: Direction,
     #[serde(with = "ColorDef")]
     pub color: Color,
+    pub team: TeamId,
     pub hand: Hand,
+    /// Modifiers from played cards, on top of those from the player's entities
+    pub timed_modifiers: Vec<TimedPlayerModifier>,
+    /// Gathered by the game loop, so the client sees the same as the server
+    pub modifiers: PlayerModifiers,
 }
 
 impl ServerPlayer {
-    pub fn new(direction: Direction, color: Color, deck: Vec<Card>) -> Self {
+    pub fn new(direction: Direction, color: Color, team: TeamId, deck: Vec<Card>) -> Self {
         Self {
             direction,
             color,
+            team,
             hand: Hand::new(deck),
+            timed_modifiers: Vec::new(),
+            modifiers: PlayerModifiers::default(),
         }
     }
 }
+
+/// Whether the players are the same or on the same team
+pub fn are_allies(
+    players: &HashMap<PlayerId, ServerPlayer>,
+    player_id_a: PlayerId,
+    player_id_b: PlayerId,
+) -> bool {
+    player_id_a == player_id_b
+        || players
+            .get(&player_id_a)
+            .zip(players.get(&player_id_b))
+            .is_some_and(|(player_a, player_b)| player_a.team == player_b.team)
+}
@@ -1,6 +1,6 @@
 use rand::Rng;
 
-pub fn shuffle_vec<T>(vec: &mut Vec<T>) {
+pub fn shuffle_vec<T>(vec: &mut [T]) {
     let mut rng = rand::thread_rng();
     for i in 0..vec.len() {
         let j = rng.gen_range(0..vec.len());
@@ -1,4 +1,4 @@
-use crate::level_config::get_prototype_level_config;
+use crate::game_state::StaticGameState;
 use macroquad::{
     camera::{set_camera, set_default_camera, Camera2D},
     input::mouse_position,
@@ -22,15 +22,15 @@ impl Default for ViewState {
     }
 }
 
-pub fn get_level_rect() -> Rect {
-    let level_width = get_prototype_level_config().level_width as f32;
-    let level_height = get_prototype_level_config().level_height as f32;
+pub fn get_level_rect(static_game_state: &StaticGameState) -> Rect {
+    let level_width = static_game_state.level_width as f32;
+    let level_height = static_game_state.level_height as f32;
     Rect::new(0.0, 0.0, level_width, level_height)
 }
 
-pub fn get_level_aspect() -> f32 {
-    let level_width = get_prototype_level_config().level_width as f32;
-    let level_height = get_prototype_level_config().level_height as f32;
+pub fn get_level_aspect(static_game_state: &StaticGameState) -> f32 {
+    let level_width = static_game_state.level_width as f32;
+    let level_height = static_game_state.level_height as f32;
     level_width / level_height
 }
 
@@ -54,9 +54,13 @@ impl ViewState {
         set_default_camera();
     }
 
-    pub fn set_scrolling_level_camera(&mut self, display_space: Rect) {
-        let level_width = get_prototype_level_config().level_width as f32;
-        let level_height = get_prototype_level_config().level_height as f32;
+    pub fn set_scrolling_level_camera(
+        &mut self,
+        static_game_state: &StaticGameState,
+        display_space: Rect,
+    ) {
+        let level_width = static_game_state.level_width as f32;
+        let level_height = static_game_state.level_height as f32;
 
         let draw_area_aspect =
             (screen_width() * display_space.w) / (screen_height() * display_space.h);
@@ -1,11 +1,16 @@
 use crate::{
+    card::Card,
     component_movement::{get_detection_range, PathTargetSetter},
-    entity::{Entity, EntityInstance},
+    entity::{Entity, EntityInstance, EntityState, EntityTag},
     entity_blueprint::EntityBlueprint,
     game_state::{DynamicGameState, SemiStaticGameState, StaticGameState},
     get_unit_spawnpoints::get_unit_spawnpoints,
     ids::{BuildingLocationId, EntityId, PathId, PlayerId},
-    play_target::{BuildingLocationTarget, UnitSpawnpointTarget},
+    path_graph::{
+        distance_along_graph_to, distance_to_graph_end, paths_are_connected, BranchPolicy,
+    },
+    play_target::{BuildingLocationTarget, PathNodeTarget, UnitSpawnpointTarget},
+    ruleset::Ruleset,
     serde_defs::Vec2Def,
 };
 use itertools::Itertools;
@@ -18,6 +23,24 @@ pub struct BuildingLocation {
     pub pos: Vec2,
     pub entity_id: Option<EntityId>,
     pub zoning: Zoning,
+    pub reservation: Option<BuildingLocationReservation>,
+}
+
+impl BuildingLocation {
+    pub fn is_available(&self) -> bool {
+        self.entity_id.is_none() && self.reservation.is_none()
+    }
+}
+
+/// Held by a builder on its way to the location, released when the building is placed or the
+/// builder dies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildingLocationReservation {
+    pub builder_id: EntityId,
+    pub owner: PlayerId,
+    pub card: Card,
+    /// Energy paid for the card, which modifiers may have made differ from its base cost
+    pub energy_paid: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -79,56 +102,86 @@ pub fn path_length(path: &Vec<(f32, f32)>, start_idx: usize, stop_idx: usize) ->
     for i in start_idx..stop_idx {
         let (x1, y1) = path[i];
         let (x2, y2) = path[i + 1];
-        length += (x2 - x1).powi(2) + (y2 - y1).powi(2);
+        length += ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
     }
-    length.sqrt()
+    length
 }
 
+/// Direction and distance of the shortest route along the graph from the spawnpoint to a node
+/// within `detection_range` of `to_pos`
 pub fn path_length_from_spawnpoint(
     static_game_state: &StaticGameState,
     spawnpoint: &UnitSpawnpointTarget,
     to_pos: Vec2,
     detection_range: f32,
-) -> Option<(usize, f32)> {
-    let path = static_game_state.paths.get(&spawnpoint.path_id).unwrap();
-    let path_nodes_in_range_predicate = |(_, (x, y)): &(usize, &(f32, f32))| {
-        ((*x - to_pos.x).powi(2) + (*y - to_pos.y).powi(2)) < detection_range.powi(2)
-    };
-    let path_nodes_in_range = path
-        .iter()
-        .enumerate()
-        .filter(path_nodes_in_range_predicate);
-
-    let path_lengths =
-        path_nodes_in_range.map(|(idx, _)| (idx, path_length(path, spawnpoint.path_idx, idx)));
-
-    path_lengths.min_by(|(_, len_a), (_, len_b)| len_a.partial_cmp(len_b).unwrap())
+) -> Option<(Direction, f32)> {
+    [Direction::Positive, Direction::Negative]
+        .into_iter()
+        .filter_map(|direction| {
+            distance_along_graph_to(
+                static_game_state,
+                spawnpoint.path_id,
+                spawnpoint.path_idx,
+                &direction,
+                to_pos,
+                detection_range,
+            )
+            .map(|len| (direction, len))
+        })
+        .min_by(|(_, len_a), (_, len_b)| len_a.partial_cmp(len_b).unwrap())
 }
 
+/// Building locations near the nodes of `path_id` and of every path connected to it, with the
+/// path and index of the node they are near
 pub fn world_get_building_locations_on_path(
     path_id: PathId,
     search_range: f32,
     static_game_state: &StaticGameState,
     semi_static_game_state: &SemiStaticGameState,
-) -> Vec<(BuildingLocationId, BuildingLocation, usize)> {
-    let path = static_game_state.paths.get(&path_id).unwrap();
-    path.iter()
-        .enumerate()
-        .flat_map(|(path_idx, (x, y))| {
-            let pos = Vec2 { x: *x, y: *y };
-            semi_static_game_state
-                .building_locations()
-                .iter()
-                .filter(move |(_building_location_id, building_location)| {
-                    (building_location.pos - pos).length_squared() < search_range.powi(2)
-                })
-                .map(move |(building_location_id, building_location)| {
-                    (*building_location_id, building_location.clone(), path_idx)
-                })
+) -> Vec<(BuildingLocationId, BuildingLocation, PathId, usize)> {
+    let mut connected_path_ids = vec![path_id];
+    let mut idx = 0;
+    while let Some(connected_path_id) = connected_path_ids.get(idx).copied() {
+        let mut neighbour_ids = static_game_state
+            .paths
+            .keys()
+            .filter(|other_id| {
+                !connected_path_ids.contains(other_id)
+                    && paths_are_connected(static_game_state, connected_path_id, **other_id)
+            })
+            .copied()
+            .collect_vec();
+        neighbour_ids.sort_by_key(|other_id| other_id.0);
+        connected_path_ids.extend(neighbour_ids);
+        idx += 1;
+    }
+    connected_path_ids
+        .into_iter()
+        .flat_map(|path_id| {
+            let path = static_game_state.paths.get(&path_id).unwrap();
+            path.iter().enumerate().flat_map(move |(path_idx, (x, y))| {
+                let pos = Vec2 { x: *x, y: *y };
+                semi_static_game_state
+                    .building_locations()
+                    .iter()
+                    .filter(move |(_building_location_id, building_location)| {
+                        (building_location.pos - pos).length_squared() < search_range.powi(2)
+                    })
+                    .map(move |(building_location_id, building_location)| {
+                        (
+                            *building_location_id,
+                            building_location.clone(),
+                            path_id,
+                            path_idx,
+                        )
+                    })
+            })
         })
         .collect_vec()
 }
 
+/// The owned or planned building closest to the end of the graph in the player's direction,
+/// among those connected to `path_id`
 pub fn world_get_furthest_planned_or_existing_building(
     path_id: PathId,
     player_id: PlayerId,
@@ -136,50 +189,76 @@ pub fn world_get_furthest_planned_or_existing_building(
     static_game_state: &StaticGameState,
     semi_static_game_state: &SemiStaticGameState,
     dynamic_game_state: &DynamicGameState,
-) -> Option<(BuildingLocationId, BuildingLocation, usize)> {
+) -> Option<(BuildingLocationId, BuildingLocation, PathId, usize)> {
     let player_direction = dynamic_game_state
         .players
-        .get(&player_id)
-        .unwrap()
+        .get(&player_id)?
         .direction
         .clone();
-    let building_locations_along_path = world_get_building_locations_on_path(
+    world_get_building_locations_on_path(
         path_id,
         search_range,
         static_game_state,
         semi_static_game_state,
-    );
-    let mut owned_building_locations_along_path = building_locations_along_path.iter().filter(
-        |(building_location_id, building_location, _)| {
-            find_entity(&dynamic_game_state.entities, building_location.entity_id)
-                .is_some_and(|entity| entity.owner == player_id)
-                || dynamic_game_state.entities.iter().any(|entity_instance| {
-                    entity_instance.owner == player_id
-                        && entity_instance
-                            .entity
-                            .building_to_construct
-                            .as_ref()
-                            .is_some_and(|(building_spot_target, _)| {
-                                building_spot_target.id == *building_location_id
-                            })
-                })
+    )
+    .into_iter()
+    .filter(|(building_location_id, building_location, _, _)| {
+        find_entity(&dynamic_game_state.entities, building_location.entity_id)
+            .is_some_and(|entity| entity.owner == player_id)
+            || dynamic_game_state.entities.iter().any(|entity_instance| {
+                entity_instance.owner == player_id
+                    && entity_instance
+                        .entity
+                        .building_to_construct
+                        .as_ref()
+                        .is_some_and(|(building_spot_target, _)| {
+                            building_spot_target.id == *building_location_id
+                        })
+            })
+    })
+    .min_by(
+        |(_, _, path_id_a, path_idx_a), (_, _, path_id_b, path_idx_b)| {
+            let remaining_distance = |path_id: PathId, path_idx: usize| {
+                distance_to_graph_end(static_game_state, path_id, path_idx, &player_direction)
+            };
+            remaining_distance(*path_id_a, *path_idx_a)
+                .total_cmp(&remaining_distance(*path_id_b, *path_idx_b))
         },
-    );
-    match player_direction {
-        Direction::Positive => owned_building_locations_along_path.last().cloned(),
-        Direction::Negative => owned_building_locations_along_path.next().cloned(),
-    }
+    )
 }
 
 pub fn find_entity_mut(
     entities: &mut [EntityInstance],
     id: Option<EntityId>,
 ) -> Option<&mut EntityInstance> {
-    return id.and_then(|id| entities.iter_mut().find(|entity| entity.id == id));
+    id.and_then(|id| entities.iter_mut().find(|entity| entity.id == id))
 }
 
 pub fn find_entity(entities: &[EntityInstance], id: Option<EntityId>) -> Option<&EntityInstance> {
-    return id.and_then(|id| entities.iter().find(|entity| entity.id == id));
+    id.and_then(|id| entities.iter().find(|entity| entity.id == id))
+}
+
+/// The path node closest to `pos`, as a spawnpoint walking in `direction`
+pub fn world_get_closest_spawnpoint(
+    static_game_state: &StaticGameState,
+    pos: Vec2,
+    direction: Direction,
+) -> Option<UnitSpawnpointTarget> {
+    static_game_state
+        .paths
+        .iter()
+        .flat_map(|(path_id, path)| {
+            path.iter()
+                .enumerate()
+                .map(move |(path_idx, (x, y))| (*path_id, path_idx, Vec2::new(*x, *y)))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.distance(pos).partial_cmp(&b.distance(pos)).unwrap())
+        .map(|(path_id, path_idx, _)| UnitSpawnpointTarget {
+            path_id,
+            path_idx,
+            direction,
+            branch_policy: BranchPolicy::default(),
+        })
 }
 
 pub fn world_place_path_entity(
@@ -194,38 +273,82 @@ pub fn world_place_path_entity(
         return None;
     };
     let pos = get_path_pos(static_game_state, target.path_id, target.path_idx);
-    movement.path_target_setter = Some(PathTargetSetter {
-        path_state: Some(target.into()),
-    });
+    // Flying units take off from the spawnpoint, but don't follow the path
+    if movement.flight_target_setter.is_none() {
+        movement.path_target_setter = Some(PathTargetSetter {
+            path_state: Some(target.into()),
+        });
+    }
     let entity_instance = entity.instantiate(owner, pos);
     let entity_id = entity_instance.id;
     dynamic_game_state.entities.spawn(entity_instance);
     Some(entity_id)
 }
 
+/// Places a ground effect zone, which is removed when its time runs out
+pub fn world_place_zone(
+    dynamic_game_state: &mut DynamicGameState,
+    entity: Entity,
+    owner: PlayerId,
+    pos: Vec2,
+) -> EntityId {
+    debug_assert!(entity.tag == EntityTag::Zone);
+    let entity_instance = entity.instantiate(owner, pos);
+    let entity_id = entity_instance.id;
+    dynamic_game_state.entities.spawn(entity_instance);
+    entity_id
+}
+
+/// Places a trap on the path node, hidden from the other players
+pub fn world_place_trap(
+    static_game_state: &StaticGameState,
+    dynamic_game_state: &mut DynamicGameState,
+    target: &PathNodeTarget,
+    entity: Entity,
+    owner: PlayerId,
+) -> EntityId {
+    debug_assert!(entity.tag == EntityTag::Trap);
+    let pos = get_path_pos(static_game_state, target.path_id, target.path_idx);
+    let entity_instance = entity.instantiate(owner, pos);
+    let entity_id = entity_instance.id;
+    dynamic_game_state.entities.spawn(entity_instance);
+    entity_id
+}
+
 pub fn world_place_builder(
     static_game_state: &StaticGameState,
-    semi_static_game_state: &SemiStaticGameState,
+    semi_static_game_state: &mut SemiStaticGameState,
     dynamic_game_state: &mut DynamicGameState,
     target: &BuildingLocationTarget,
     builder_entity: Entity,
     building_blueprint: EntityBlueprint,
+    card: Card,
     owner: PlayerId,
 ) -> bool {
     let mut builder_entity = builder_entity;
-    let building_pos = semi_static_game_state
+    let building_location = semi_static_game_state
         .building_locations()
         .get(&target.id)
-        .unwrap()
-        .pos;
+        .unwrap();
+    if !building_location.is_available() {
+        return false;
+    }
+    let building_pos = building_location.pos;
     let Some(detection_range) = get_detection_range(&builder_entity) else {
         debug_assert!(false);
         return false;
     };
+    // The card is paid for right after it is played, with the modifiers of this moment
+    let energy_paid = dynamic_game_state
+        .players
+        .get(&owner)
+        .map_or(card.get_card_data().energy_cost, |player| {
+            player.modifiers.energy_cost(&card)
+        });
 
     builder_entity.building_to_construct = Some((target.clone(), building_blueprint.clone()));
 
-    let Some((target_path_idx, mut spawnpoint_target)) =
+    let Some((direction, mut spawnpoint_target)) =
         get_unit_spawnpoints(owner, static_game_state, dynamic_game_state)
             .iter()
             .filter_map(|spawnpoint| {
@@ -235,45 +358,158 @@ pub fn world_place_builder(
                     building_pos,
                     detection_range,
                 )
-                .map(|(target_path_idx, len)| (spawnpoint, target_path_idx, len))
+                .map(|(direction, len)| (spawnpoint, direction, len))
             })
             .min_by(|(_, _, len_a), (_, _, len_b)| len_a.partial_cmp(len_b).unwrap())
-            .map(|(spawnpoint, target_path_idx, _len)| (target_path_idx, spawnpoint.clone()))
+            .map(|(spawnpoint, direction, _len)| (direction, spawnpoint.clone()))
     else {
         return false;
     };
 
-    spawnpoint_target.direction = if target_path_idx < spawnpoint_target.path_idx {
-        Direction::Negative
-    } else {
-        Direction::Positive
-    };
-    world_place_path_entity(
+    spawnpoint_target.direction = direction;
+    spawnpoint_target.branch_policy = BranchPolicy::Towards(building_pos);
+    let Some(builder_id) = world_place_path_entity(
         static_game_state,
         dynamic_game_state,
         spawnpoint_target,
         builder_entity,
         owner,
-    );
+    ) else {
+        return false;
+    };
+    semi_static_game_state
+        .building_locations_mut()
+        .get_mut(&target.id)
+        .unwrap()
+        .reservation = Some(BuildingLocationReservation {
+        builder_id,
+        owner,
+        card,
+        energy_paid,
+    });
     true
 }
 
 pub fn world_place_building(
     semi_static_game_state: &mut SemiStaticGameState,
     dynamic_game_state: &mut DynamicGameState,
-    entity: Entity,
+    mut entity: Entity,
     building_location_id: &BuildingLocationId,
     owner: PlayerId,
 ) -> Option<EntityId> {
-    let BuildingLocation { pos, entity_id, .. } = semi_static_game_state
+    let BuildingLocation {
+        pos,
+        entity_id,
+        reservation,
+        ..
+    } = semi_static_game_state
         .building_locations_mut()
         .get_mut(building_location_id)
         .unwrap();
     if entity_id.is_some() {
         return None;
     }
+    if let Some(reservation) = reservation.take() {
+        entity.energy_invested += reservation.energy_paid;
+    }
     let entity_instance = entity.instantiate(owner, *pos);
     *entity_id = Some(entity_instance.id);
     dynamic_game_state.entities.spawn(entity_instance);
     *entity_id
 }
+
+fn find_own_building_mut(
+    dynamic_game_state: &mut DynamicGameState,
+    entity_id: EntityId,
+    owner: PlayerId,
+) -> Option<&mut EntityInstance> {
+    find_entity_mut(&mut dynamic_game_state.entities, Some(entity_id)).filter(|entity_instance| {
+        entity_instance.owner == owner
+            && entity_instance.entity.tag == EntityTag::Tower
+            && entity_instance.state != EntityState::Dead
+    })
+}
+
+pub fn world_sell_building(
+    dynamic_game_state: &mut DynamicGameState,
+    ruleset: &Ruleset,
+    entity_id: EntityId,
+    owner: PlayerId,
+) -> bool {
+    let Some(entity_instance) = find_own_building_mut(dynamic_game_state, entity_id, owner) else {
+        return false;
+    };
+    entity_instance.state = EntityState::Dead;
+    let refund =
+        (entity_instance.entity.energy_invested as f32 * ruleset.sell_refund_fraction) as i32;
+    let Some(player) = dynamic_game_state.players.get_mut(&owner) else {
+        return false;
+    };
+    player
+        .hand
+        .refund_energy(refund, ruleset, &player.modifiers);
+    true
+}
+
+/// Keeps what happened to the building so far: damage, buffs from cards, status effects and
+/// stealth state
+fn carry_over_to_upgrade(entity: &Entity, upgraded_entity: &mut Entity) {
+    let health_fraction = entity.health.health / entity.health.max_health;
+    upgraded_entity.health.health = upgraded_entity.health.max_health * health_fraction;
+    upgraded_entity.health.extra_health_buffs = entity.health.extra_health_buffs.clone();
+    upgraded_entity.energy_invested = entity.energy_invested;
+    upgraded_entity.status_effects = entity.status_effects.clone();
+    if upgraded_entity.stealth.is_some() {
+        upgraded_entity.stealth = entity.stealth.clone().or(upgraded_entity.stealth.take());
+    }
+    // Buffs are added to every attack, so the first attack has them all
+    if let Some(attack) = entity.attacks.first() {
+        for upgraded_attack in upgraded_entity.attacks.iter_mut() {
+            upgraded_attack.damage_buffs = attack.damage_buffs.clone();
+            upgraded_attack.attack_speed_buffs = attack.attack_speed_buffs.clone();
+            upgraded_attack.range_buffs = attack.range_buffs.clone();
+        }
+    }
+}
+
+pub fn world_upgrade_building(
+    dynamic_game_state: &mut DynamicGameState,
+    entity_id: EntityId,
+    owner: PlayerId,
+) -> bool {
+    let Some(energy) = dynamic_game_state
+        .players
+        .get(&owner)
+        .map(|player| player.hand.energy)
+    else {
+        return false;
+    };
+    let Some(entity_instance) = find_own_building_mut(dynamic_game_state, entity_id, owner) else {
+        return false;
+    };
+    if entity_instance.entity.construction.is_some() {
+        return false;
+    }
+    let Some((upgrade_blueprint, upgrade_cost)) = entity_instance
+        .entity
+        .blueprint
+        .as_ref()
+        .and_then(|blueprint| blueprint.get_upgrade())
+    else {
+        return false;
+    };
+    if energy < upgrade_cost {
+        return false;
+    }
+    let mut upgraded_entity = upgrade_blueprint.create();
+    carry_over_to_upgrade(&entity_instance.entity, &mut upgraded_entity);
+    upgraded_entity.energy_invested += upgrade_cost;
+    entity_instance.entity = upgraded_entity;
+    dynamic_game_state
+        .players
+        .get_mut(&owner)
+        .unwrap()
+        .hand
+        .energy -= upgrade_cost;
+    true
+}
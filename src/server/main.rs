@@ -1,25 +1,84 @@
 use common::config::SERVER_PORT;
 use common::entity_blueprint::EntityBlueprint;
 use common::game_state::ServerControlledGameState;
-use common::gameplay_config::{STARTING_ENERGY, STARTING_HAND_SIZE};
 use common::ids::PlayerId;
-use common::level_config::get_prototype_level_config;
+use common::level_config::LevelPreset;
 use common::message_acknowledgement::AckUdpSocket;
-use common::network::{hash_client_addr, ClientMessage, ServerMessage, ServerMessageData};
+use common::network::{
+    hash_client_addr, ClientMessage, ProtocolHello, ServerMessage, ServerMessageData,
+};
+use common::network_simulation::NetworkConditions;
 use common::play_target::{PlayArgs, PlayTarget};
+use common::ruleset::RulesetPreset;
 use common::server_player::ServerPlayer;
+use common::world::{world_sell_building, world_upgrade_building};
 use common::*;
 use itertools::Itertools;
 use std::collections::hash_map;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::{SocketAddr, UdpSocket};
+use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
+fn get_preset_arg<Preset>(flag: &str, kind: &str, presets: Vec<Preset>) -> Preset
+where
+    Preset: FromStr + Default + std::fmt::Debug,
+{
+    let args = std::env::args().collect_vec();
+    let Some(value) = args
+        .iter()
+        .position(|arg| arg == flag)
+        .and_then(|idx| args.get(idx + 1))
+    else {
+        return Preset::default();
+    };
+    Preset::from_str(value).unwrap_or_else(|_| {
+        panic!(
+            "Unknown {} '{}', expected one of {:?}",
+            kind, value, presets
+        )
+    })
+}
+
+fn send_initial_game_state(
+    ack_udp_socket: &mut AckUdpSocket<ServerMessage, ClientMessage>,
+    game_state: &ServerControlledGameState,
+    client_addr: &SocketAddr,
+) {
+    ack_udp_socket.send_to(
+        ServerMessage {
+            metadata: game_state.game_metadata.clone(),
+            data: ServerMessageData::StaticGameState(game_state.static_game_state.clone()),
+        },
+        client_addr,
+        true,
+    );
+    ack_udp_socket.send_to(
+        ServerMessage {
+            metadata: game_state.game_metadata.clone(),
+            data: ServerMessageData::SemiStaticGameState(game_state.semi_static_game_state.clone()),
+        },
+        client_addr,
+        true,
+    );
+}
+
 fn main() -> std::io::Result<()> {
     let mut game_state = ServerControlledGameState::default();
     let mut client_addresses = HashMap::<PlayerId, SocketAddr>::new();
+    let mut spectator_addresses = HashMap::<PlayerId, SocketAddr>::new();
+    let mut accepted_addresses = HashSet::<SocketAddr>::new();
+    let server_hello = ProtocolHello::current();
 
-    game_state.load_level_config(get_prototype_level_config());
+    let level_preset = get_preset_arg("--level", "level", LevelPreset::iter().collect_vec());
+    game_state.load_level_config(level_preset.create());
+    println!("Using level {:?}", level_preset);
+    game_state.static_game_state.ruleset =
+        get_preset_arg("--ruleset", "ruleset", RulesetPreset::iter().collect_vec()).create();
+    println!(
+        "Using ruleset {:?}",
+        game_state.static_game_state.ruleset.preset
+    );
 
     let server_ip = local_ip_address::local_ip()
         .map(|ip| format!("{}:{}", ip, SERVER_PORT))
@@ -31,6 +90,10 @@ fn main() -> std::io::Result<()> {
         .unwrap();
     let mut ack_udp_socket =
         AckUdpSocket::<ServerMessage, ClientMessage>::new(udp_socket, Duration::from_secs(1));
+    if let Some(conditions) = NetworkConditions::from_args() {
+        println!("Simulating network conditions {:?}", conditions);
+        ack_udp_socket.simulate_network_conditions(Some(conditions));
+    }
     let mut time = SystemTime::now();
 
     loop {
@@ -41,14 +104,33 @@ fn main() -> std::io::Result<()> {
         while let Some((client_message, client_addr)) = ack_udp_socket.receive() {
             let client_id = hash_client_addr(&client_addr);
             match client_message {
+                ClientMessage::Hello(client_hello) => {
+                    let data = match server_hello.check_compatible(&client_hello) {
+                        Ok(()) => {
+                            accepted_addresses.insert(client_addr);
+                            ServerMessageData::HelloAccepted
+                        }
+                        Err(reason) => {
+                            println!("Rejected client {}: {}", client_addr, reason);
+                            ServerMessageData::HelloRejected(reason)
+                        }
+                    };
+                    ack_udp_socket.send_to(
+                        ServerMessage {
+                            metadata: game_state.game_metadata.clone(),
+                            data,
+                        },
+                        &client_addr,
+                        false,
+                    );
+                }
+                _ if !accepted_addresses.contains(&client_addr) => {}
                 ClientMessage::PlayCard(card_id, target) => {
                     if let Some(card_from_idx) = &mut game_state
                         .dynamic_game_state
                         .players
                         .get_mut(&client_id)
-                        .unwrap()
-                        .hand
-                        .try_get(card_id)
+                        .and_then(|player| player.hand.try_get(card_id, &player.modifiers))
                     {
                         let played = card_from_idx.get_card_data().play_fn.exec(PlayArgs::<
                             PlayTarget,
@@ -61,30 +143,48 @@ fn main() -> std::io::Result<()> {
                         });
 
                         if played {
-                            game_state
+                            let player = game_state
                                 .dynamic_game_state
                                 .players
                                 .get_mut(&client_id)
-                                .unwrap()
-                                .hand
-                                .play(card_id);
+                                .unwrap();
+                            player.hand.play(card_id, &player.modifiers);
                         }
                     }
                 }
+                ClientMessage::SellBuilding(entity_id) => {
+                    world_sell_building(
+                        &mut game_state.dynamic_game_state,
+                        &game_state.static_game_state.ruleset,
+                        entity_id,
+                        client_id,
+                    );
+                }
+                ClientMessage::UpgradeBuilding(entity_id) => {
+                    world_upgrade_building(
+                        &mut game_state.dynamic_game_state,
+                        entity_id,
+                        client_id,
+                    );
+                }
                 ClientMessage::JoinGame(deck) => {
+                    spectator_addresses.remove(&client_id);
                     if let hash_map::Entry::Vacant(vacant_entry) = client_addresses.entry(client_id)
                     {
                         vacant_entry.insert(client_addr);
-                        if let Some(available_config) = get_prototype_level_config()
+                        if let Some(available_config) = level_preset
+                            .create()
                             .player_configs
                             .get(game_state.dynamic_game_state.players.len())
                         {
-                            let (base_pos, available_direction, available_color) = available_config;
+                            let (base_pos, available_direction, available_color, available_team) =
+                                available_config;
                             game_state.dynamic_game_state.players.insert(
                                 client_id,
                                 ServerPlayer::new(
                                     available_direction.clone(),
                                     *available_color,
+                                    *available_team,
                                     deck,
                                 ),
                             );
@@ -93,95 +193,80 @@ fn main() -> std::io::Result<()> {
                                 .players
                                 .get_mut(&client_id)
                                 .unwrap();
-                            server_player.hand.energy = STARTING_ENERGY;
-                            for _ in 0..STARTING_HAND_SIZE {
-                                server_player.hand.draw();
-                            }
+                            server_player
+                                .hand
+                                .deal_starting_hand(&game_state.static_game_state.ruleset);
                             let base_entity = EntityBlueprint::Base
                                 .create()
                                 .instantiate(client_id, *base_pos);
                             game_state.dynamic_game_state.entities.spawn(base_entity);
                         }
                     }
-                    ack_udp_socket.send_to(
-                        ServerMessage {
-                            metadata: game_state.game_metadata.clone(),
-                            data: ServerMessageData::StaticGameState(
-                                game_state.static_game_state.clone(),
-                            ),
-                        },
-                        &client_addr,
-                        true,
-                    );
-                    ack_udp_socket.send_to(
-                        ServerMessage {
-                            metadata: game_state.game_metadata.clone(),
-                            data: ServerMessageData::SemiStaticGameState(
-                                game_state.semi_static_game_state.clone(),
-                            ),
-                        },
-                        &client_addr,
-                        true,
-                    );
+                    send_initial_game_state(&mut ack_udp_socket, &game_state, &client_addr);
+                }
+                ClientMessage::SpectateGame => {
+                    if !client_addresses.contains_key(&client_id) {
+                        spectator_addresses.insert(client_id, client_addr);
+                    }
+                    send_initial_game_state(&mut ack_udp_socket, &game_state, &client_addr);
                 }
             }
         }
 
-        for client_addr in client_addresses.values() {
-            ack_udp_socket.send_to(
-                ServerMessage {
-                    metadata: game_state.game_metadata.clone(),
-                    data: ServerMessageData::DynamicGameState(
-                        game_state.dynamic_game_state.clone(),
-                    ),
-                },
-                client_addr,
-                false,
-            );
-        }
-        if game_state.semi_static_game_state.dirty {
-            game_state.semi_static_game_state.dirty = false;
-            for client_addr in client_addresses.values() {
-                ack_udp_socket.send_to(
-                    ServerMessage {
+        let recipients = client_addresses
+            .values()
+            .chain(spectator_addresses.values())
+            .collect_vec();
+        let has_concealed_entities = game_state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .any(|entity_instance| entity_instance.entity.is_concealed());
+        if has_concealed_entities {
+            // Players don't receive the hidden entities of enemies, spectators see everything
+            for (client_id, client_addr) in client_addresses.iter() {
+                ack_udp_socket.broadcast(
+                    &ServerMessage {
                         metadata: game_state.game_metadata.clone(),
-                        data: ServerMessageData::SemiStaticGameState(
-                            game_state.semi_static_game_state.clone(),
+                        data: ServerMessageData::DynamicGameState(
+                            game_state.dynamic_game_state.visible_to(*client_id),
                         ),
                     },
-                    client_addr,
+                    [client_addr],
                     false,
                 );
             }
         }
+        ack_udp_socket.broadcast(
+            &ServerMessage {
+                metadata: game_state.game_metadata.clone(),
+                data: ServerMessageData::DynamicGameState(game_state.dynamic_game_state.clone()),
+            },
+            match has_concealed_entities {
+                true => spectator_addresses.values().collect_vec(),
+                false => recipients.clone(),
+            },
+            false,
+        );
+        if game_state.semi_static_game_state.dirty {
+            game_state.semi_static_game_state.dirty = false;
+            ack_udp_socket.broadcast(
+                &ServerMessage {
+                    metadata: game_state.game_metadata.clone(),
+                    data: ServerMessageData::SemiStaticGameState(
+                        game_state.semi_static_game_state.clone(),
+                    ),
+                },
+                recipients.iter().copied(),
+                false,
+            );
+        }
 
         game_state.game_metadata.server_tick += 1;
-        for (client_id, client) in game_state.dynamic_game_state.players.iter_mut() {
-            let draw_speed_buffs = game_state
-                .dynamic_game_state
-                .entities
-                .iter()
-                .filter_map(|entity_instance| {
-                    if entity_instance.owner != *client_id {
-                        return None;
-                    }
-                    entity_instance.entity.draw_speed_buff.clone()
-                })
-                .collect_vec();
-            let energy_generation_buffs = game_state
-                .dynamic_game_state
-                .entities
-                .iter()
-                .filter_map(|entity_instance| {
-                    if entity_instance.owner != *client_id {
-                        return None;
-                    }
-                    entity_instance.entity.energy_generation_buff.clone()
-                })
-                .collect_vec();
-            client
+        for player in game_state.dynamic_game_state.players.values_mut() {
+            player
                 .hand
-                .step(dt, &draw_speed_buffs, &energy_generation_buffs);
+                .step(dt, &game_state.static_game_state.ruleset, &player.modifiers);
         }
 
         game_loop::update_game_state(&mut game_state, dt);
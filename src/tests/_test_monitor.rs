@@ -13,22 +13,46 @@ use macroquad::{
 use serde::{Deserialize, Serialize};
 
 pub mod condition;
+pub mod test_aura_effects;
 pub mod test_basic_movement_and_attack;
 pub mod test_black_death;
+pub mod test_broadcast;
+pub mod test_building_reservation;
+pub mod test_construction;
 pub mod test_continuous_buff_application;
+pub mod test_damage_types;
 pub mod test_dynamite_man;
 pub mod test_environment;
+pub mod test_flying_units;
 pub mod test_governor;
+pub mod test_ground_effect_zones;
 pub mod test_higher_motivation;
 pub mod test_iron_mine;
 pub mod test_lightning_strike;
 pub mod test_meteor;
+pub mod test_network_simulation;
+pub mod test_path_graph;
+pub mod test_player_modifiers;
+pub mod test_priest;
+pub mod test_projectiles;
 pub mod test_protector_can_attack_ranger;
+pub mod test_protocol_handshake;
 pub mod test_ranger_stops_to_attack;
 pub mod test_reinforced_doors;
+pub mod test_ruleset;
+pub mod test_sell_and_upgrade;
 pub mod test_small_tower;
+pub mod test_spawner;
+pub mod test_spell_immunity;
 pub mod test_spy;
+pub mod test_status_effects;
 pub mod test_steady_aim;
+pub mod test_stealth;
+pub mod test_target_priority;
+pub mod test_teams;
+pub mod test_traps;
+pub mod test_triggers;
+pub mod test_unit_collision;
 pub mod test_watchtower;
 
 pub const TEST_CLIENT_ADDR: &str = "127.0.0.1:12346";
@@ -79,17 +79,10 @@ impl Condition {
                     == vec![*entity_id]
             }
             Condition::PlayerWon(player_id) => {
-                test_environment
-                    .state
-                    .dynamic_game_state
-                    .entities
-                    .iter()
-                    .filter_map(|entity_instance| {
-                        matches!(entity_instance.entity.tag, EntityTag::Base)
-                            .then_some(entity_instance.owner)
-                    })
-                    .collect_vec()
-                    == vec![*player_id]
+                let dynamic_game_state = &test_environment.state.dynamic_game_state;
+                dynamic_game_state.get_winning_team().is_some_and(|team| {
+                    dynamic_game_state.players.get(player_id).unwrap().team == team
+                })
             }
         }
     }
@@ -0,0 +1,106 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_movement::{Movement, MovementSpeed},
+        entity::Entity,
+        entity_blueprint::EntityBlueprint,
+        ids::{EntityId, PlayerId},
+    };
+    use macroquad::math::Vec2;
+
+    fn has_full_health(entity: &Entity) -> bool {
+        entity.health.health == entity.health.max_health
+    }
+
+    fn is_damaged(entity: &Entity) -> bool {
+        entity.health.health < entity.health.max_health
+    }
+
+    /// Without attacks and standing still, out of melee range, only auras can deal damage
+    fn place_standing_unarmed(
+        test_env: &mut TestEnvironment,
+        player_id: PlayerId,
+        entity: Entity,
+        x: f32,
+    ) -> EntityId {
+        let entity_instance = Entity {
+            attacks: Vec::new(),
+            movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+            ..entity
+        }
+        .instantiate(player_id, Vec2::new(x, 200.0));
+        let entity_id = entity_instance.id;
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .spawn(entity_instance);
+        entity_id
+    }
+
+    #[test]
+    fn test_shrine_regenerates_allies() {
+        let mut test_env = TestEnvironment::default();
+
+        test_env.place_building(test_env.player_a, EntityBlueprint::Shrine.create());
+
+        let mut criminal = EntityBlueprint::StreetCriminal.create();
+        criminal.health.health = 150.0;
+        let criminal_id = test_env.play_entity(test_env.player_a, criminal);
+
+        let simulation_result =
+            test_env.simulate_until(Condition::EntitySatisfies(criminal_id, has_full_health));
+        assert!(simulation_result.is_ok());
+    }
+
+    #[test]
+    fn test_plague_rat_damages_enemies() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+
+        place_standing_unarmed(
+            &mut test_env,
+            player_a,
+            EntityBlueprint::PlagueRat.create(),
+            600.0,
+        );
+        let ally_id = place_standing_unarmed(
+            &mut test_env,
+            player_a,
+            EntityBlueprint::HomesickWarrior.create(),
+            650.0,
+        );
+        let enemy_id = place_standing_unarmed(
+            &mut test_env,
+            player_b,
+            EntityBlueprint::HomesickWarrior.create(),
+            750.0,
+        );
+
+        test_env.add_percistent(Condition::EntitySatisfies(ally_id, has_full_health), true);
+
+        let simulation_result =
+            test_env.simulate_until(Condition::EntitySatisfies(enemy_id, is_damaged));
+        assert!(simulation_result.is_ok());
+    }
+
+    #[test]
+    fn test_continuous_damage_can_kill() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+
+        place_standing_unarmed(
+            &mut test_env,
+            player_a,
+            EntityBlueprint::PlagueRat.create(),
+            600.0,
+        );
+        let mut enemy = EntityBlueprint::HomesickWarrior.create();
+        enemy.health.health = 10.0;
+        let enemy_id = place_standing_unarmed(&mut test_env, player_b, enemy, 750.0);
+
+        let simulation_result = test_env.simulate_until(Condition::EntityIsDead(enemy_id));
+        assert!(simulation_result.is_ok());
+    }
+}
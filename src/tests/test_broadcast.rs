@@ -0,0 +1,39 @@
+#[cfg(test)]
+pub mod test {
+    use common::message_acknowledgement::AckUdpSocket;
+    use std::{net::UdpSocket, thread::sleep, time::Duration};
+
+    fn bind() -> AckUdpSocket<u32, u32> {
+        let udp_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        udp_socket.set_nonblocking(true).unwrap();
+        AckUdpSocket::new(udp_socket, Duration::from_millis(50))
+    }
+
+    fn receive_all(ack_udp_socket: &mut AckUdpSocket<u32, u32>) -> Vec<u32> {
+        sleep(Duration::from_millis(10));
+        std::iter::from_fn(|| ack_udp_socket.receive().map(|(message, _)| message)).collect()
+    }
+
+    #[test]
+    fn test_broadcast_resends_only_to_unacknowledged_recipients() {
+        let mut server = bind();
+        let mut acking_client = bind();
+        let mut silent_client = bind();
+        let silent_client_addr = silent_client.local_addr().unwrap();
+
+        server.broadcast(
+            &7,
+            [&acking_client.local_addr().unwrap(), &silent_client_addr],
+            true,
+        );
+        assert_eq!(receive_all(&mut acking_client), vec![7]);
+
+        // The silent client doesn't read its socket yet, so it never acknowledges
+        receive_all(&mut server);
+
+        sleep(Duration::from_millis(60));
+        server.send_queued();
+        assert_eq!(receive_all(&mut acking_client), Vec::<u32>::new());
+        assert_eq!(receive_all(&mut silent_client), vec![7, 7]);
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        card::{Card, CardInstance, CardTag},
+        entity::EntityState,
+        ids::CardInstanceId,
+        player_modifier::{PlayerModifier, TimedPlayerModifier},
+        ruleset::FailedConstructionCompensation,
+        world::find_entity,
+    };
+
+    fn test_env_with_builder() -> TestEnvironment {
+        // Builders need a path node near the building location
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![vec![(100.0, 200.0), (600.0, 200.0), (1100.0, 200.0)]];
+        let mut test_env = TestEnvironment::new(level_config);
+        test_env.play_card(test_env.player_a, Card::Tower);
+        test_env
+    }
+
+    /// Plays a tower with a discount that runs out while the builder is walking
+    fn test_env_with_discounted_builder() -> TestEnvironment {
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![vec![(100.0, 200.0), (600.0, 200.0), (1100.0, 200.0)]];
+        let mut test_env = TestEnvironment::new(level_config);
+        test_env
+            .state
+            .dynamic_game_state
+            .players
+            .get_mut(&test_env.player_a)
+            .unwrap()
+            .timed_modifiers
+            .push(TimedPlayerModifier::new(
+                PlayerModifier::CardCost(CardTag::Building, -1),
+                0.5,
+            ));
+        assert!(test_env.simulate_frame().is_ok());
+        test_env.play_card(test_env.player_a, Card::Tower);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        test_env
+    }
+
+    fn kill_builder(test_env: &mut TestEnvironment) {
+        let builder = test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter_mut()
+            .find(|e| e.entity.building_to_construct.is_some())
+            .unwrap();
+        builder.state = EntityState::Dead;
+        assert!(test_env.simulate_frame().is_ok());
+    }
+
+    fn location_is_available(test_env: &TestEnvironment) -> bool {
+        test_env
+            .state
+            .semi_static_game_state
+            .building_locations()
+            .values()
+            .all(|building_location| building_location.is_available())
+    }
+
+    #[test]
+    fn test_reserved_location_rejects_builders() {
+        let mut test_env = test_env_with_builder();
+        assert!(!location_is_available(&test_env));
+        assert!(!test_env.try_play_card_at(test_env.player_b, Card::Tower, None));
+    }
+
+    #[test]
+    fn test_builder_death_releases_reservation_and_refunds_energy() {
+        let mut test_env = test_env_with_builder();
+        kill_builder(&mut test_env);
+        assert!(location_is_available(&test_env));
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert_eq!(hand.energy, Card::Tower.get_card_data().energy_cost);
+    }
+
+    #[test]
+    fn test_builder_death_refunds_discounted_energy() {
+        let mut test_env = test_env_with_discounted_builder();
+        kill_builder(&mut test_env);
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert_eq!(hand.energy, Card::Tower.get_card_data().energy_cost - 1);
+    }
+
+    #[test]
+    fn test_building_invests_discounted_energy() {
+        let mut test_env = test_env_with_discounted_builder();
+        assert!(test_env.simulate_until(Condition::NoUnitsAlive).is_ok());
+        let building_id = test_env
+            .state
+            .semi_static_game_state
+            .building_locations()
+            .values()
+            .next()
+            .unwrap()
+            .entity_id;
+        let building = find_entity(&test_env.state.dynamic_game_state.entities, building_id);
+        assert_eq!(
+            building.unwrap().entity.energy_invested,
+            Card::Tower.get_card_data().energy_cost - 1
+        );
+    }
+
+    #[test]
+    fn test_builder_death_returns_card() {
+        let mut test_env = test_env_with_builder();
+        test_env
+            .state
+            .static_game_state
+            .ruleset
+            .failed_construction_compensation = FailedConstructionCompensation::ReturnCard;
+        let hand = &mut test_env
+            .state
+            .dynamic_game_state
+            .players
+            .get_mut(&test_env.player_a)
+            .unwrap()
+            .hand;
+        hand.played.push(CardInstance {
+            id: CardInstanceId::new(),
+            card: Card::Tower,
+        });
+        kill_builder(&mut test_env);
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert!(hand.played.is_empty());
+        assert_eq!(hand.cards.len(), 1);
+        assert_eq!(hand.energy, 0);
+    }
+
+    #[test]
+    fn test_builder_death_returns_card_reshuffled_into_deck() {
+        let mut test_env = test_env_with_builder();
+        test_env
+            .state
+            .static_game_state
+            .ruleset
+            .failed_construction_compensation = FailedConstructionCompensation::ReturnCard;
+        let hand = &mut test_env
+            .state
+            .dynamic_game_state
+            .players
+            .get_mut(&test_env.player_a)
+            .unwrap()
+            .hand;
+        hand.deck.push(CardInstance {
+            id: CardInstanceId::new(),
+            card: Card::Tower,
+        });
+        kill_builder(&mut test_env);
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert!(hand.deck.is_empty());
+        assert_eq!(hand.cards.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_death_returns_card_when_it_is_gone() {
+        let mut test_env = test_env_with_builder();
+        test_env
+            .state
+            .static_game_state
+            .ruleset
+            .failed_construction_compensation = FailedConstructionCompensation::ReturnCard;
+        kill_builder(&mut test_env);
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert_eq!(hand.cards.len(), 1);
+        assert_eq!(hand.cards[0].card, Card::Tower);
+    }
+
+    #[test]
+    fn test_reservation_released_when_building_is_placed() {
+        let mut test_env = test_env_with_builder();
+        assert!(test_env.simulate_until(Condition::NoUnitsAlive).is_ok());
+        let building_location = test_env
+            .state
+            .semi_static_game_state
+            .building_locations()
+            .values()
+            .next()
+            .unwrap();
+        assert!(building_location.entity_id.is_some());
+        assert!(building_location.reservation.is_none());
+        let hand = &test_env.state.dynamic_game_state.players[&test_env.player_a].hand;
+        assert_eq!(hand.energy, 0);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        card::Card,
+        component_construction::{BuildTime, Construction},
+        component_status_effects::StatusEffect,
+        entity::{EntityState, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        ids::EntityId,
+        world::find_entity_mut,
+    };
+
+    fn place_under_construction(test_env: &mut TestEnvironment, pos: (f32, f32)) -> EntityId {
+        let mut tower = EntityBlueprint::Tower.create();
+        Construction::start(&mut tower, BuildTime::Regular);
+        test_env.place_building_at(test_env.player_a, tower, pos)
+    }
+
+    #[test]
+    fn test_builder_starts_construction() {
+        // Builders need a path node near the building location
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![vec![(100.0, 200.0), (600.0, 200.0), (1100.0, 200.0)]];
+        let mut test_env = TestEnvironment::new(level_config);
+        test_env.play_card(test_env.player_a, Card::Tower);
+        let find_tower = |test_env: &TestEnvironment| {
+            test_env
+                .state
+                .dynamic_game_state
+                .entities
+                .iter()
+                .find(|e| e.entity.tag == EntityTag::Tower && e.owner == test_env.player_a)
+                .map(|e| e.id)
+        };
+        let mut tower_id = None;
+        while tower_id.is_none() {
+            assert!(test_env.simulate_frame().is_ok());
+            tower_id = find_tower(&test_env);
+        }
+        let tower_id = tower_id.unwrap();
+        let health = &test_env.get_entity(tower_id).entity.health;
+        assert!(health.health < health.max_health * 0.2);
+
+        assert!(test_env.simulate_frames(3).is_ok());
+        assert!(Condition::EntityIsInState(tower_id, EntityState::Building).is_met(&test_env));
+        assert!(test_env
+            .simulate_until(Condition::EntitySatisfies(tower_id, |entity| {
+                entity.construction.is_none()
+            }))
+            .is_ok());
+        let health = &test_env.get_entity(tower_id).entity.health;
+        assert!(health.health > health.max_health * 0.99);
+    }
+
+    #[test]
+    fn test_building_can_not_attack_under_construction() {
+        let mut test_env = TestEnvironment::default();
+        place_under_construction(&mut test_env, (1050.0, 150.0));
+        let enemy = test_env.play_entity(test_env.player_b, EntityBlueprint::DemonWolf.create());
+        find_entity_mut(&mut test_env.state.dynamic_game_state.entities, Some(enemy))
+            .unwrap()
+            .entity
+            .status_effects
+            .apply(StatusEffect::stun(100.0));
+        let enemy_health = test_env.get_entity(enemy).entity.health.health;
+
+        assert!(test_env
+            .simulate_for(BuildTime::Regular.to_f32() - 1.0)
+            .is_ok());
+        assert_eq!(
+            test_env.get_entity(enemy).entity.health.health,
+            enemy_health
+        );
+        assert!(test_env.simulate_for(3.0).is_ok());
+        assert!(test_env.get_entity(enemy).entity.health.health < enemy_health);
+    }
+
+    #[test]
+    fn test_building_can_be_destroyed_under_construction() {
+        let mut test_env = TestEnvironment::default();
+        let tower_id = place_under_construction(&mut test_env, (600.0, 150.0));
+        assert!(test_env.simulate_for(1.0).is_ok());
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(tower_id),
+        )
+        .unwrap()
+        .entity
+        .health
+        .deal_damage(1000.0);
+        assert!(test_env
+            .simulate_until(Condition::EntityIsDead(tower_id))
+            .is_ok());
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::DamageType, component_health::Health, entity::EntityTag,
+        entity_blueprint::EntityBlueprint,
+    };
+
+    #[test]
+    fn test_armor_and_magic_resistance() {
+        let health = Health::new(100.0)
+            .with_armor(0.5)
+            .with_magic_resistance(0.25);
+        assert_eq!(health.get_damage_taken(100.0, &DamageType::Physical), 50.0);
+        assert_eq!(health.get_damage_taken(100.0, &DamageType::Piercing), 75.0);
+        assert_eq!(health.get_damage_taken(100.0, &DamageType::Siege), 100.0);
+        assert_eq!(health.get_damage_taken(100.0, &DamageType::Magic), 75.0);
+    }
+
+    #[test]
+    fn test_tag_damage_multipliers() {
+        let elf_warrior = EntityBlueprint::ElfWarrior.create();
+        let attack = elf_warrior.attacks.first().unwrap();
+        assert_eq!(
+            attack.get_damage_against(&EntityTag::FlyingUnit),
+            1.5 * attack.get_damage()
+        );
+        assert_eq!(
+            attack.get_damage_against(&EntityTag::Unit),
+            attack.get_damage()
+        );
+    }
+
+    #[test]
+    fn test_siege_damage_destroys_tower() {
+        let mut test_env = TestEnvironment::default();
+
+        let tower_id = test_env.place_building_at(
+            test_env.player_b,
+            EntityBlueprint::Tower.create(),
+            (600.0, 200.0),
+        );
+        let mut dynamite_man = EntityBlueprint::DynamiteMan.create();
+        dynamite_man.health = Health::new(1000.0);
+        test_env.play_entity(test_env.player_a, dynamite_man);
+
+        let simulation_result = test_env.simulate_until(Condition::EntityIsDead(tower_id));
+        assert!(simulation_result.is_ok());
+    }
+}
@@ -1,8 +1,7 @@
 #[cfg(test)]
-
 pub mod test {
     use crate::{condition::Condition, test_environment::test::TestEnvironment};
-    use common::entity_blueprint::EntityBlueprint;
+    use common::{entity::EntityState, entity_blueprint::EntityBlueprint};
 
     #[test]
     fn test_dynamite_man() {
@@ -19,4 +18,23 @@ pub mod test {
         let simulation_result = test_env.simulate_until(Condition::NoUnitsAlive);
         assert!(simulation_result.is_ok());
     }
+
+    #[test]
+    fn test_dynamite_man_spares_allies() {
+        let mut test_env = TestEnvironment::default();
+
+        let dynamite_man_id =
+            test_env.play_entity(test_env.player_a, EntityBlueprint::DynamiteMan.create());
+        let ally_id = test_env.play_entity(test_env.player_a, EntityBlueprint::Priest.create());
+        test_env.play_entity(test_env.player_b, EntityBlueprint::StreetCriminal.create());
+
+        assert!(test_env
+            .simulate_until(Condition::EntityIsInState(
+                dynamite_man_id,
+                EntityState::Dead
+            ))
+            .is_ok());
+        // The blast deals 300 damage, the criminal may have hit the ally before it
+        assert!(test_env.get_entity(ally_id).entity.health.health > 0.0);
+    }
 }
@@ -7,14 +7,17 @@ pub mod test {
         game_loop,
         game_state::ServerControlledGameState,
         get_unit_spawnpoints::get_unit_spawnpoints,
-        ids::{BuildingLocationId, EntityId, PlayerId},
+        ids::{BuildingLocationId, EntityId, PlayerId, TeamId},
         level_config::LevelConfig,
         message_acknowledgement::AckUdpSocket,
         network::{
             send_dynamic_game_state, send_semi_static_game_state, send_static_game_state,
             ServerMessage,
         },
-        play_target::{BuildingLocationTarget, PlayArgs, PlayFn, PlayTarget, WorldPosTarget},
+        play_target::{
+            get_path_node_targets, BuildingLocationTarget, PlayArgs, PlayFn, PlayTarget,
+            WorldPosTarget,
+        },
         server_player::ServerPlayer,
         world::{
             find_entity, world_place_building, world_place_path_entity, BuildingLocation,
@@ -26,7 +29,6 @@ pub mod test {
         math::Vec2,
     };
     use std::{
-        iter::zip,
         net::{Ipv4Addr, SocketAddr, UdpSocket},
         thread::sleep,
         time::Duration,
@@ -75,6 +77,8 @@ pub mod test {
         pub state: ServerControlledGameState,
         pub player_a: PlayerId,
         pub player_b: PlayerId,
+        /// One player for every player config, starting with `player_a` and `player_b`
+        pub player_ids: Vec<PlayerId>,
         pub speed: f32,
         pub sim_time_s: f32,
         pub timeout_s: f32,
@@ -95,32 +99,42 @@ pub mod test {
                 spawn_point_radius: 256.0,
                 nearby_radius: 256.0,
                 player_configs: vec![
-                    (Vec2::new(50.0, 200.0), Direction::Positive, RED),
-                    (Vec2::new(1150.0, 200.0), Direction::Negative, BLUE),
+                    (Vec2::new(50.0, 200.0), Direction::Positive, RED, TeamId(0)),
+                    (
+                        Vec2::new(1150.0, 200.0),
+                        Direction::Negative,
+                        BLUE,
+                        TeamId(1),
+                    ),
                 ],
                 building_locations: vec![(Zoning::Normal, (600.0, 100.0))],
                 paths: vec![vec![(100.0, 200.0), (1100.0, 200.0)]],
             }
         }
         pub fn new(level_config: LevelConfig) -> Self {
+            let player_ids: Vec<PlayerId> = level_config
+                .player_configs
+                .iter()
+                .map(|_| PlayerId::new())
+                .collect();
             let mut test_environment = Self {
                 network_state: TestEnvironmentNetworkState::default(),
                 state: ServerControlledGameState::default(),
-                player_a: PlayerId::new(),
-                player_b: PlayerId::new(),
+                player_a: player_ids[0],
+                player_b: player_ids[1],
+                player_ids: player_ids.clone(),
                 speed: 1.0,
                 sim_time_s: 0.0,
                 timeout_s: 120.0,
                 percistent_condtions: Vec::new(),
             };
 
-            for (player_id, (base_pos, direction, color)) in zip(
-                [test_environment.player_a, test_environment.player_b],
-                &level_config.player_configs,
-            ) {
+            for (player_id, (base_pos, direction, color, team)) in
+                player_ids.into_iter().zip(&level_config.player_configs)
+            {
                 test_environment.state.dynamic_game_state.players.insert(
                     player_id,
-                    ServerPlayer::new(direction.clone(), *color, Vec::new()),
+                    ServerPlayer::new(direction.clone(), *color, *team, Vec::new()),
                 );
                 let mut base_entity = EntityBlueprint::Base
                     .create()
@@ -242,6 +256,7 @@ pub mod test {
                         entity_id: None,
                         pos: Vec2::new(pos.0, pos.1),
                         zoning: Zoning::Normal,
+                        reservation: None,
                     },
                 );
             world_place_building(
@@ -263,6 +278,15 @@ pub mod test {
             card: Card,
             target: Option<PlayTarget>,
         ) {
+            assert!(self.try_play_card_at(player_id, card, target));
+        }
+
+        pub fn try_play_card_at(
+            &mut self,
+            player_id: PlayerId,
+            card: Card,
+            target: Option<PlayTarget>,
+        ) -> bool {
             let play_fn = card.get_card_data().play_fn;
             let target = match target {
                 Some(target) => target,
@@ -291,17 +315,22 @@ pub mod test {
                         })
                     }
                     PlayFn::WorldPos(_) => PlayTarget::WorldPos(WorldPosTarget { x: 0.0, y: 0.0 }),
+                    PlayFn::PathNode(_) => PlayTarget::PathNode(
+                        get_path_node_targets(&self.state.static_game_state)
+                            .first()
+                            .unwrap()
+                            .clone(),
+                    ),
                     PlayFn::Entity(_) => todo!(),
                 },
             };
-            let play_succeded = play_fn.exec(PlayArgs::<PlayTarget> {
+            play_fn.exec(PlayArgs::<PlayTarget> {
                 target: &target,
                 owner: player_id,
                 static_game_state: &self.state.static_game_state,
                 semi_static_game_state: &mut self.state.semi_static_game_state,
                 dynamic_game_state: &mut self.state.dynamic_game_state,
-            });
-            assert!(play_succeded);
+            })
         }
         pub fn get_entity(&self, entity_id: EntityId) -> &EntityInstance {
             find_entity(&self.state.dynamic_game_state.entities, Some(entity_id)).unwrap()
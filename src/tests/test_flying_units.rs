@@ -0,0 +1,55 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::Attack,
+        component_health::Health,
+        entity::{Entity, EntityState},
+    };
+
+    fn test_env_with_winding_path() -> TestEnvironment {
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![vec![(100.0, 200.0), (600.0, 380.0), (1100.0, 200.0)]];
+        TestEnvironment::new(level_config)
+    }
+
+    #[test]
+    fn test_flying_unit_flies_straight_to_enemy_base() {
+        let mut test_env = test_env_with_winding_path();
+        let flying_unit_id = test_env.play_entity(
+            test_env.player_a,
+            Entity {
+                health: Health::new(100.0),
+                ..Entity::default_flying_unit()
+            },
+        );
+        assert!(test_env.simulate_for(8.0).is_ok());
+        let pos = test_env.get_entity_position(flying_unit_id);
+        assert!(pos.x > 400.0);
+        assert!((pos.y - 200.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_flying_unit_detours_to_target() {
+        let mut test_env = test_env_with_winding_path();
+        let player_b = test_env.player_b;
+        let mut tower = Entity::default_tower();
+        tower.health = Health::new(1000.0);
+        test_env.place_building_at(player_b, tower, (600.0, 50.0));
+        let flying_unit_id = test_env.play_entity(
+            test_env.player_a,
+            Entity {
+                health: Health::new(100.0),
+                attacks: vec![Attack::default_flying()],
+                ..Entity::default_flying_unit()
+            },
+        );
+        assert!(test_env
+            .simulate_until(Condition::EntityIsInState(
+                flying_unit_id,
+                EntityState::Attacking
+            ))
+            .is_ok());
+        assert!(test_env.get_entity_position(flying_unit_id).y < 200.0);
+    }
+}
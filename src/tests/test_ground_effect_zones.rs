@@ -0,0 +1,161 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        card::Card,
+        component_attack::Attack,
+        component_health::Health,
+        component_movement::{Movement, MovementSpeed},
+        component_status_effects::StatusEffectKind,
+        entity::{AbilityFlag, Entity, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        enum_flags::{flags, EnumFlags},
+        ids::{EntityId, PlayerId},
+        play_target::{PlayTarget, WorldPosTarget},
+        world::find_entity_mut,
+    };
+
+    fn play_zone(test_env: &mut TestEnvironment, player_id: PlayerId, card: Card, x: f32, y: f32) {
+        test_env.play_card_at(
+            player_id,
+            card,
+            Some(PlayTarget::WorldPos(WorldPosTarget { x, y })),
+        );
+    }
+
+    fn play_stationary_unit(test_env: &mut TestEnvironment, player_id: PlayerId) -> EntityId {
+        test_env.play_entity(
+            player_id,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+                attacks: Vec::new(),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        )
+    }
+
+    fn zone_count(test_env: &TestEnvironment) -> usize {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|entity_instance| entity_instance.entity.tag == EntityTag::Zone)
+            .count()
+    }
+
+    #[test]
+    fn test_tar_pit_slows_enemies_only() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_zone(&mut test_env, player_a, Card::TarPit, 100.0, 200.0);
+        play_zone(&mut test_env, player_a, Card::TarPit, 1100.0, 200.0);
+        let ally_id = play_stationary_unit(&mut test_env, player_a);
+        let enemy_id = play_stationary_unit(&mut test_env, player_b);
+        assert!(test_env.simulate_for(0.5).is_ok());
+        let is_slowed = |test_env: &TestEnvironment, entity_id| {
+            test_env
+                .get_entity(entity_id)
+                .entity
+                .status_effects
+                .has(StatusEffectKind::Slow)
+        };
+        assert!(is_slowed(&test_env, enemy_id));
+        assert!(!is_slowed(&test_env, ally_id));
+    }
+
+    #[test]
+    fn test_burning_ground_spares_flying_units() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_zone(&mut test_env, player_a, Card::BurningGround, 1100.0, 200.0);
+        let unit_id = play_stationary_unit(&mut test_env, player_b);
+        let flying_unit_id = test_env.play_entity(
+            player_b,
+            Entity {
+                health: Health::new(100.0),
+                ..Entity::default_flying_unit()
+            },
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        let health = |test_env: &TestEnvironment, entity_id| {
+            let health = &test_env.get_entity(entity_id).entity.health;
+            (health.health, health.max_health)
+        };
+        let (unit_health, unit_max_health) = health(&test_env, unit_id);
+        assert!(unit_health < unit_max_health);
+        let (flying_unit_health, flying_unit_max_health) = health(&test_env, flying_unit_id);
+        assert_eq!(flying_unit_health, flying_unit_max_health);
+    }
+
+    #[test]
+    fn test_zones_spare_spell_immune() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_zone(&mut test_env, player_a, Card::BurningGround, 1100.0, 200.0);
+        play_zone(&mut test_env, player_a, Card::TarPit, 1100.0, 200.0);
+        let immune_id = test_env.play_entity(
+            player_b,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+                attacks: Vec::new(),
+                ability_flags: flags![AbilityFlag::SpellImmune],
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        let immune_entity = &test_env.get_entity(immune_id).entity;
+        assert_eq!(immune_entity.health.health, immune_entity.health.max_health);
+        assert!(!immune_entity.status_effects.has(StatusEffectKind::Slow));
+    }
+
+    #[test]
+    fn test_holy_circle_heals_allies() {
+        let mut test_env = TestEnvironment::default();
+        let player_a = test_env.player_a;
+        let unit_id = play_stationary_unit(&mut test_env, player_a);
+        assert!(test_env.simulate_frame().is_ok());
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(unit_id),
+        )
+        .unwrap()
+        .entity
+        .health
+        .health = 1.0;
+        play_zone(&mut test_env, player_a, Card::HolyCircle, 100.0, 200.0);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert!(test_env.get_entity(unit_id).entity.health.health > 10.0);
+    }
+
+    #[test]
+    fn test_zone_expires() {
+        let mut test_env = TestEnvironment::default();
+        let player_a = test_env.player_a;
+        play_zone(&mut test_env, player_a, Card::TarPit, 600.0, 200.0);
+        assert!(test_env.simulate_frame().is_ok());
+        assert_eq!(zone_count(&test_env), 1);
+        let seconds = EntityBlueprint::TarPit
+            .create()
+            .seconds_left_to_live
+            .unwrap();
+        assert!(test_env.simulate_for(seconds + 0.1).is_ok());
+        assert_eq!(zone_count(&test_env), 0);
+    }
+
+    #[test]
+    fn test_zones_are_not_targeted() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        let mut tower = Entity::default_tower();
+        tower.health = Health::new(1000.0);
+        tower.attacks = vec![Attack::default_ranged()];
+        let tower_id = test_env.place_building_at(player_b, tower, (600.0, 100.0));
+        play_zone(&mut test_env, player_a, Card::TarPit, 600.0, 200.0);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(zone_count(&test_env), 1);
+        assert!(test_env.get_entity(tower_id).entity.attacks[0]
+            .current_target_id
+            .is_none());
+    }
+}
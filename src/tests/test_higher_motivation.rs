@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 pub mod test {
     use crate::{condition::Condition, test_environment::test::TestEnvironment};
     use common::{card::Card, entity_blueprint::EntityBlueprint};
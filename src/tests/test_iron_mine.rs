@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 pub mod test {
     use crate::test_environment::test::TestEnvironment;
     use common::entity_blueprint::EntityBlueprint;
@@ -1,20 +1,22 @@
 #[cfg(test)]
-
 pub mod test {
     use crate::{condition::Condition, test_environment::test::TestEnvironment};
     use common::{
         card::Card,
+        component_movement::{Movement, MovementSpeed},
+        entity::Entity,
         entity_blueprint::EntityBlueprint,
         play_target::{PlayTarget, WorldPosTarget},
+        world::find_entity_mut,
     };
     use macroquad::math::Vec2;
 
     #[test]
     fn test_lightning_strike() {
         let mut test_env = TestEnvironment::default();
-        
+
         test_env.play_entity(test_env.player_a, EntityBlueprint::StreetCriminal.create());
-        
+
         test_env.play_entity(test_env.player_b, EntityBlueprint::StreetCriminal.create());
         assert!(test_env.simulate_for(0.5).is_ok());
         let criminal_b =
@@ -35,4 +37,32 @@ pub mod test {
         let simulation_result = test_env.simulate_until(Condition::PlayerWon(test_env.player_a));
         assert!(simulation_result.is_ok());
     }
+
+    #[test]
+    fn test_lightning_strike_uses_level_nearby_radius() {
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.nearby_radius = 50.0;
+        let mut test_env = TestEnvironment::new(level_config);
+        let player_b = test_env.player_b;
+        let unit_id = test_env.play_entity(
+            player_b,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        );
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(unit_id),
+        )
+        .unwrap()
+        .pos = Vec2::new(600.0, 200.0);
+        test_env.play_card_at(
+            test_env.player_a,
+            Card::LightningStrike,
+            Some(PlayTarget::WorldPos(WorldPosTarget { x: 700.0, y: 200.0 })),
+        );
+        let health = &test_env.get_entity(unit_id).entity.health;
+        assert_eq!(health.health, health.max_health);
+    }
 }
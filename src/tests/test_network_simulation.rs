@@ -0,0 +1,59 @@
+#[cfg(test)]
+pub mod test {
+    use common::{message_acknowledgement::AckUdpSocket, network_simulation::NetworkConditions};
+    use std::{
+        collections::HashSet,
+        net::UdpSocket,
+        str::FromStr,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn test_network_conditions_parsing() {
+        let conditions =
+            NetworkConditions::from_str("latency=150,loss=0.1,reordering=0.05").unwrap();
+        assert_eq!(conditions.latency, Duration::from_millis(150));
+        assert_eq!(conditions.jitter, Duration::ZERO);
+        assert_eq!(conditions.loss, 0.1);
+        assert_eq!(conditions.duplication, 0.0);
+        assert_eq!(conditions.reordering, 0.05);
+        assert!(NetworkConditions::from_str("lag=100").is_err());
+        assert!(NetworkConditions::from_str("latency=high").is_err());
+    }
+
+    #[test]
+    fn test_acked_messages_survive_bad_network() {
+        let bind = || {
+            let udp_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+            udp_socket.set_nonblocking(true).unwrap();
+            AckUdpSocket::<u32, u32>::new(udp_socket, Duration::from_millis(30))
+        };
+        let mut sender = bind();
+        let mut receiver = bind();
+        let conditions = NetworkConditions {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(10),
+            loss: 0.5,
+            duplication: 0.2,
+            reordering: 0.2,
+        };
+        sender.simulate_network_conditions(Some(conditions.clone()));
+        receiver.simulate_network_conditions(Some(conditions));
+
+        let receiver_addr = receiver.local_addr().unwrap();
+        for i in 0..20 {
+            sender.queue(i, &receiver_addr, true);
+        }
+        let mut received = HashSet::new();
+        let start_time = SystemTime::now();
+        while received.len() < 20 && start_time.elapsed().unwrap() < Duration::from_secs(10) {
+            sender.send_queued();
+            while sender.receive().is_some() {}
+            while let Some((message, _)) = receiver.receive() {
+                received.insert(message);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(received, (0..20).collect());
+    }
+}
@@ -0,0 +1,217 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        card::Card,
+        component_movement::{get_path_id, Movement, MovementSpeed},
+        entity::Entity,
+        entity_blueprint::EntityBlueprint,
+        get_unit_spawnpoints::get_unit_spawnpoints,
+        ids::{EntityId, PathId, PlayerId},
+        level_config::LevelConfig,
+        path_graph::BranchPolicy,
+        world::{get_path_pos, world_place_path_entity, Zoning},
+    };
+    use macroquad::math::Vec2;
+
+    fn junction_level_config() -> LevelConfig {
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![
+            vec![(100.0, 200.0), (400.0, 200.0)],
+            // The north lane is shorter than the south lane
+            vec![(400.0, 200.0), (700.0, 0.0), (1000.0, 200.0)],
+            vec![(400.0, 200.0), (700.0, 600.0), (1000.0, 200.0)],
+            vec![(1000.0, 200.0), (1100.0, 200.0)],
+        ];
+        level_config.building_locations = vec![(Zoning::Normal, (700.0, 520.0))];
+        level_config
+    }
+
+    fn test_env_with_junctions() -> TestEnvironment {
+        TestEnvironment::new(junction_level_config())
+    }
+
+    fn path_through(test_env: &TestEnvironment, node: (f32, f32)) -> PathId {
+        *test_env
+            .state
+            .static_game_state
+            .paths
+            .iter()
+            .find(|(_, path)| path.contains(&node))
+            .unwrap()
+            .0
+    }
+
+    fn play_unit(
+        test_env: &mut TestEnvironment,
+        player_id: PlayerId,
+        spawn_node: (f32, f32),
+        branch_policy: BranchPolicy,
+    ) -> EntityId {
+        play_entity_from(
+            test_env,
+            player_id,
+            spawn_node,
+            branch_policy,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                attacks: Vec::new(),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        )
+    }
+
+    fn play_entity_from(
+        test_env: &mut TestEnvironment,
+        player_id: PlayerId,
+        spawn_node: (f32, f32),
+        branch_policy: BranchPolicy,
+        entity: Entity,
+    ) -> EntityId {
+        let static_game_state = &test_env.state.static_game_state;
+        let mut spawnpoint = get_unit_spawnpoints(
+            player_id,
+            static_game_state,
+            &test_env.state.dynamic_game_state,
+        )
+        .into_iter()
+        .find(|spawnpoint| {
+            get_path_pos(static_game_state, spawnpoint.path_id, spawnpoint.path_idx)
+                == Vec2::from(spawn_node)
+        })
+        .unwrap();
+        spawnpoint.branch_policy = branch_policy;
+        world_place_path_entity(
+            &test_env.state.static_game_state,
+            &mut test_env.state.dynamic_game_state,
+            spawnpoint,
+            entity,
+            player_id,
+        )
+        .unwrap()
+    }
+
+    fn reaches_path(test_env: &mut TestEnvironment, entity_id: EntityId, path_id: PathId) -> bool {
+        test_env
+            .simulate(|env| get_path_id(&env.get_entity(entity_id).entity) == Some(path_id))
+            .is_ok()
+    }
+
+    #[test]
+    fn test_shortest_to_enemy_base() {
+        let mut test_env = test_env_with_junctions();
+        let player_a = test_env.player_a;
+        let unit_id = play_unit(
+            &mut test_env,
+            player_a,
+            (100.0, 200.0),
+            BranchPolicy::ShortestToEnemyBase,
+        );
+        let north_lane = path_through(&test_env, (700.0, 0.0));
+        assert!(reaches_path(&mut test_env, unit_id, north_lane));
+    }
+
+    #[test]
+    fn test_fixed_lane() {
+        let mut test_env = test_env_with_junctions();
+        let player_a = test_env.player_a;
+        let south_lane = path_through(&test_env, (700.0, 600.0));
+        let unit_id = play_unit(
+            &mut test_env,
+            player_a,
+            (100.0, 200.0),
+            BranchPolicy::FixedLane(south_lane),
+        );
+        assert!(reaches_path(&mut test_env, unit_id, south_lane));
+        let tail = path_through(&test_env, (1100.0, 200.0));
+        assert!(reaches_path(&mut test_env, unit_id, tail));
+    }
+
+    #[test]
+    fn test_least_defended() {
+        let mut test_env = test_env_with_junctions();
+        let player_a = test_env.player_a;
+        let player_b = test_env.player_b;
+        let mut tower = EntityBlueprint::Tower.create();
+        tower.attacks = Vec::new();
+        test_env.place_building_at(player_b, tower, (700.0, 50.0));
+        let unit_id = play_unit(
+            &mut test_env,
+            player_a,
+            (100.0, 200.0),
+            BranchPolicy::LeastDefended,
+        );
+        let south_lane = path_through(&test_env, (700.0, 600.0));
+        assert!(reaches_path(&mut test_env, unit_id, south_lane));
+    }
+
+    #[test]
+    fn test_branching_walking_negative() {
+        let mut test_env = test_env_with_junctions();
+        let player_b = test_env.player_b;
+        let unit_id = play_unit(
+            &mut test_env,
+            player_b,
+            (1100.0, 200.0),
+            BranchPolicy::ShortestToEnemyBase,
+        );
+        let north_lane = path_through(&test_env, (700.0, 0.0));
+        let trunk = path_through(&test_env, (100.0, 200.0));
+        assert!(reaches_path(&mut test_env, unit_id, north_lane));
+        assert!(reaches_path(&mut test_env, unit_id, trunk));
+    }
+
+    #[test]
+    fn test_spawnpoint_on_junction_leads_away_from_it() {
+        let mut level_config = junction_level_config();
+        level_config.player_configs[0].0 = Vec2::new(400.0, 250.0);
+        let test_env = TestEnvironment::new(level_config);
+        let spawnpoints = get_unit_spawnpoints(
+            test_env.player_a,
+            &test_env.state.static_game_state,
+            &test_env.state.dynamic_game_state,
+        );
+        assert_eq!(spawnpoints.len(), 1);
+        assert_eq!(spawnpoints[0].path_idx, 0);
+    }
+
+    #[test]
+    fn test_builder_takes_lane_to_building() {
+        let mut test_env = test_env_with_junctions();
+        test_env.play_card(test_env.player_a, Card::Tower);
+        assert!(test_env
+            .simulate(|env| env
+                .state
+                .semi_static_game_state
+                .building_locations()
+                .values()
+                .all(|building_location| building_location.entity_id.is_some()))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_protector_guards_building_past_junction() {
+        let mut test_env = test_env_with_junctions();
+        let player_a = test_env.player_a;
+        let mut tower = EntityBlueprint::Tower.create();
+        tower.attacks = Vec::new();
+        test_env.place_building_at(player_a, tower, (700.0, 540.0));
+        let protector_id = play_entity_from(
+            &mut test_env,
+            player_a,
+            (100.0, 200.0),
+            BranchPolicy::ShortestToEnemyBase,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                attacks: Vec::new(),
+                ..EntityBlueprint::HomesickWarrior.create()
+            },
+        );
+        let guarded_node = Vec2::new(700.0, 600.0);
+        assert!(test_env
+            .simulate(|env| env.get_entity(protector_id).pos.distance(guarded_node) < 1.0)
+            .is_ok());
+        assert!(test_env.simulate_for(2.0).is_ok());
+        assert!(test_env.get_entity(protector_id).pos.distance(guarded_node) < 1.0);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        buff::ArithmeticBuff,
+        card::{Card, CardTag},
+        entity_blueprint::EntityBlueprint,
+        hand::Hand,
+        player_modifier::{PlayerModifier, PlayerModifiers},
+        ruleset::RulesetPreset,
+    };
+
+    #[test]
+    fn test_limits_and_card_costs() {
+        let ruleset = RulesetPreset::Standard.create();
+        let modifiers = PlayerModifiers {
+            modifiers: vec![
+                PlayerModifier::MaxEnergy(2),
+                PlayerModifier::MaxHandSize(-3),
+                PlayerModifier::CardCost(CardTag::Building, -1),
+                PlayerModifier::CardCost(CardTag::Unit, -100),
+            ],
+        };
+        let mut hand = Hand::new(vec![Card::Tower; 20]);
+        for _ in 0..1000 {
+            hand.step(1.0, &ruleset, &modifiers);
+        }
+        assert_eq!(hand.energy, ruleset.max_energy + 2);
+        assert_eq!(hand.cards.len(), ruleset.max_hand_size - 3);
+        assert_eq!(
+            modifiers.energy_cost(&Card::Tower),
+            Card::Tower.get_card_data().energy_cost - 1
+        );
+        assert_eq!(modifiers.energy_cost(&Card::Dragon), 0);
+        assert_eq!(
+            modifiers.energy_cost(&Card::Meteor),
+            Card::Meteor.get_card_data().energy_cost
+        );
+    }
+
+    #[test]
+    fn test_draw_speed_and_draw_bonus_energy() {
+        let ruleset = RulesetPreset::Standard.create();
+        let modifiers = PlayerModifiers {
+            modifiers: vec![
+                PlayerModifier::DrawSpeed(ArithmeticBuff::new_multiplicative(2.0)),
+                PlayerModifier::EnergyGeneration(ArithmeticBuff::new_multiplicative(0.0)),
+                PlayerModifier::DrawBonusEnergy(2),
+            ],
+        };
+        let mut hand = Hand::new(vec![Card::Tower; 20]);
+        let seconds_to_draw = 1.0 / ruleset.card_draw_per_second;
+        hand.step(seconds_to_draw / 2.0, &ruleset, &modifiers);
+        assert_eq!(hand.cards.len(), 1);
+        assert_eq!(hand.energy, 2);
+    }
+
+    #[test]
+    fn test_buildings_modify_their_owner() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        test_env.place_building(player_a, EntityBlueprint::Farm.create());
+        assert!(test_env.simulate_frame().is_ok());
+        let modifiers = |env: &TestEnvironment, player_id| {
+            env.state.dynamic_game_state.players[&player_id]
+                .modifiers
+                .modifiers
+                .len()
+        };
+        assert_eq!(modifiers(&test_env, player_a), 1);
+        assert_eq!(modifiers(&test_env, player_b), 0);
+    }
+
+    #[test]
+    fn test_timed_card_effect_expires() {
+        let mut test_env = TestEnvironment::default();
+        let player_a = test_env.player_a;
+        test_env.play_card(player_a, Card::Recruitment);
+        assert!(test_env.simulate_frame().is_ok());
+        let unit_cost = |env: &TestEnvironment| {
+            env.state.dynamic_game_state.players[&player_a]
+                .modifiers
+                .energy_cost(&Card::StreetCriminal)
+        };
+        let base_cost = Card::StreetCriminal.get_card_data().energy_cost;
+        assert_eq!(unit_cost(&test_env), base_cost - 1);
+        assert!(test_env.simulate_for(30.0).is_ok());
+        assert_eq!(unit_cost(&test_env), base_cost);
+    }
+}
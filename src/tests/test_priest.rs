@@ -0,0 +1,60 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::{Attack, AttackEffect, TargetPool},
+        entity::Entity,
+        entity_blueprint::EntityBlueprint,
+    };
+
+    fn damaged_warrior(health: f32) -> Entity {
+        let mut entity = EntityBlueprint::HomesickWarrior.create();
+        entity.health.health = health;
+        entity
+    }
+
+    #[test]
+    fn test_priest_heals_most_damaged_ally() {
+        let mut test_env = TestEnvironment::default();
+        let slightly_damaged = test_env.play_entity(test_env.player_a, damaged_warrior(150.0));
+        let badly_damaged = test_env.play_entity(test_env.player_a, damaged_warrior(50.0));
+        test_env.play_entity(test_env.player_a, EntityBlueprint::Priest.create());
+
+        let simulation_result = test_env.simulate_for(1.5);
+        assert!(simulation_result.is_ok());
+        assert!(test_env.get_entity(badly_damaged).entity.health.health > 50.0);
+        assert_eq!(
+            test_env.get_entity(slightly_damaged).entity.health.health,
+            150.0
+        );
+    }
+
+    #[test]
+    fn test_melee_healer_heals_to_max() {
+        let mut test_env = TestEnvironment::default();
+        let warrior = test_env.play_entity(test_env.player_a, damaged_warrior(100.0));
+        let enemy_warrior = test_env.play_entity(test_env.player_b, damaged_warrior(100.0));
+        test_env.play_entity(
+            test_env.player_a,
+            Entity {
+                attacks: vec![Attack {
+                    damage: 30.0,
+                    effect: AttackEffect::Heal,
+                    target_pool: TargetPool::Allies,
+                    ..Attack::default()
+                }],
+                ..EntityBlueprint::BasicBuilder.create()
+            },
+        );
+
+        let simulation_result = test_env
+            .simulate_until(Condition::EntitySatisfies(warrior, |entity| {
+                entity.health.health == entity.health.max_health
+            }));
+        assert!(simulation_result.is_ok());
+        assert_eq!(
+            test_env.get_entity(enemy_warrior).entity.health.health,
+            100.0
+        );
+    }
+}
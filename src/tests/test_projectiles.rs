@@ -0,0 +1,166 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        component_attack::{Attack, AttackInterval, AttackRange},
+        component_movement::{Movement, MovementSpeed},
+        component_projectile::{ProjectileSpec, Trajectory},
+        entity::{Entity, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        ids::EntityId,
+        world::find_entity,
+    };
+
+    fn place_tower(test_env: &mut TestEnvironment, projectile: ProjectileSpec) {
+        let mut tower = EntityBlueprint::Tower.create();
+        tower.attacks = vec![Attack {
+            damage: 10.0,
+            attack_interval: AttackInterval::Custom(10.0),
+            range: AttackRange::Custom(250.0),
+            projectile,
+            ..Attack::default_ranged_tower()
+        }];
+        test_env.place_building_at(test_env.player_a, tower, (1000.0, 100.0));
+    }
+
+    /// Units without attacks, so they walk past the tower instead of stopping to attack it
+    fn play_units(
+        test_env: &mut TestEnvironment,
+        count: usize,
+        speed: MovementSpeed,
+    ) -> Vec<EntityId> {
+        (0..count)
+            .map(|_| {
+                test_env.play_entity(
+                    test_env.player_b,
+                    Entity {
+                        movement: Some(Movement::new(speed.clone())),
+                        attacks: Vec::new(),
+                        ..EntityBlueprint::StreetCriminal.create()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn damaged_count(test_env: &TestEnvironment, entity_ids: &[EntityId]) -> usize {
+        entity_ids
+            .iter()
+            .filter(|id| {
+                find_entity(&test_env.state.dynamic_game_state.entities, Some(**id))
+                    .is_none_or(|e| e.entity.health.health < e.entity.health.max_health)
+            })
+            .count()
+    }
+
+    fn bullet_exists(test_env: &TestEnvironment) -> bool {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .any(|e| e.entity.tag == EntityTag::Bullet)
+    }
+
+    fn simulate_first_projectile(test_env: &mut TestEnvironment) {
+        assert!(test_env.simulate(bullet_exists).is_ok());
+        assert!(test_env.simulate(|env| !bullet_exists(env)).is_ok());
+    }
+
+    #[test]
+    fn test_homing_projectile_hits_single_target() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 3, MovementSpeed::Custom(0.0));
+        place_tower(&mut test_env, ProjectileSpec::default());
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 1);
+    }
+
+    #[test]
+    fn test_splash_hits_all_targets_at_impact() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 3, MovementSpeed::Custom(0.0));
+        place_tower(
+            &mut test_env,
+            ProjectileSpec {
+                splash_range: Some(AttackRange::Custom(30.0)),
+                ..ProjectileSpec::default()
+            },
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 3);
+    }
+
+    #[test]
+    fn test_pierce_passes_through_targets() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 3, MovementSpeed::Custom(0.0));
+        place_tower(
+            &mut test_env,
+            ProjectileSpec {
+                pierce: 1,
+                ..ProjectileSpec::default()
+            },
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 2);
+    }
+
+    #[test]
+    fn test_ballistic_projectile_hits_stationary_target() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 1, MovementSpeed::Custom(0.0));
+        place_tower(
+            &mut test_env,
+            ProjectileSpec {
+                trajectory: Trajectory::Ballistic,
+                speed: MovementSpeed::Custom(20.0),
+                ..ProjectileSpec::default()
+            },
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 1);
+    }
+
+    #[test]
+    fn test_ballistic_projectile_misses_moving_target() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 1, MovementSpeed::Default);
+        place_tower(
+            &mut test_env,
+            ProjectileSpec {
+                trajectory: Trajectory::Ballistic,
+                speed: MovementSpeed::Custom(20.0),
+                ..ProjectileSpec::default()
+            },
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 0);
+    }
+
+    #[test]
+    fn test_catapult_splashes_group() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 3, MovementSpeed::Custom(0.0));
+        test_env.place_building_at(
+            test_env.player_a,
+            EntityBlueprint::Catapult.create(),
+            (1000.0, 100.0),
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 3);
+    }
+
+    #[test]
+    fn test_cannon_tower_pierces() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, 4, MovementSpeed::Custom(0.0));
+        test_env.place_building_at(
+            test_env.player_a,
+            EntityBlueprint::CannonTower.create(),
+            (1050.0, 150.0),
+        );
+        simulate_first_projectile(&mut test_env);
+        assert_eq!(damaged_count(&test_env, &unit_ids), 3);
+    }
+}
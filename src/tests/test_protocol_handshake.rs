@@ -0,0 +1,35 @@
+#[cfg(test)]
+pub mod test {
+    use common::network::{content_hash, ProtocolHello};
+
+    #[test]
+    fn test_protocol_handshake_accepts_same_build() {
+        assert_eq!(content_hash(), content_hash());
+        let server_hello = ProtocolHello::current();
+        assert!(server_hello
+            .check_compatible(&ProtocolHello::current())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_protocol_handshake_rejects_mismatch() {
+        let server_hello = ProtocolHello::current();
+        let old_client_hello = ProtocolHello {
+            protocol_version: server_hello.protocol_version + 1,
+            ..server_hello.clone()
+        };
+        let reason = server_hello
+            .check_compatible(&old_client_hello)
+            .unwrap_err();
+        assert!(reason.contains("Protocol version mismatch"));
+
+        let modded_client_hello = ProtocolHello {
+            content_hash: server_hello.content_hash ^ 1,
+            ..server_hello.clone()
+        };
+        let reason = server_hello
+            .check_compatible(&modded_client_hello)
+            .unwrap_err();
+        assert!(reason.contains("Card definitions differ"));
+    }
+}
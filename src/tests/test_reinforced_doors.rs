@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 pub mod test {
     use common::{card::Card, entity::EntityTag, entity_blueprint::EntityBlueprint};
 
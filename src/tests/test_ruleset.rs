@@ -0,0 +1,37 @@
+#[cfg(test)]
+pub mod test {
+    use common::{
+        card::Card, hand::Hand, player_modifier::PlayerModifiers, ruleset::RulesetPreset,
+    };
+
+    #[test]
+    fn test_ruleset_energy_cap() {
+        let ruleset = RulesetPreset::Standard.create();
+        let mut hand = Hand::new(vec![Card::Tower; 20]);
+        hand.deal_starting_hand(&ruleset);
+        assert_eq!(hand.cards.len(), ruleset.starting_hand_size as usize);
+        assert_eq!(hand.energy, ruleset.starting_energy);
+
+        for _ in 0..1000 {
+            hand.step(1.0, &ruleset, &PlayerModifiers::default());
+        }
+        assert_eq!(hand.energy, ruleset.max_energy);
+        assert_eq!(hand.cards.len(), ruleset.max_hand_size);
+    }
+
+    #[test]
+    fn test_ruleset_sandbox_infinite_energy() {
+        let ruleset = RulesetPreset::Sandbox.create();
+        let mut hand = Hand::new(vec![Card::Dragon; 10]);
+        hand.deal_starting_hand(&ruleset);
+
+        let card_id = hand.cards.first().unwrap().id;
+        assert!(hand
+            .try_play(card_id, &PlayerModifiers::default())
+            .is_some());
+        assert!(hand.energy < ruleset.max_energy);
+
+        hand.step(0.0, &ruleset, &PlayerModifiers::default());
+        assert_eq!(hand.energy, ruleset.max_energy);
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        buff::{buff_add_to_entity, ArithmeticBuff, Buff},
+        card::Card,
+        entity::EntityState,
+        entity_blueprint::EntityBlueprint,
+        ids::PlayerId,
+        world::{find_entity_mut, world_sell_building, world_upgrade_building},
+    };
+
+    fn set_energy(test_env: &mut TestEnvironment, player_id: PlayerId, energy: i32) {
+        test_env
+            .state
+            .dynamic_game_state
+            .players
+            .get_mut(&player_id)
+            .unwrap()
+            .hand
+            .energy = energy;
+    }
+
+    fn energy(test_env: &TestEnvironment, player_id: PlayerId) -> i32 {
+        test_env.state.dynamic_game_state.players[&player_id]
+            .hand
+            .energy
+    }
+
+    #[test]
+    fn test_upgrade_path() {
+        let mut test_env = TestEnvironment::default();
+        let tower_id =
+            test_env.place_building(test_env.player_a, EntityBlueprint::SmallTower.create());
+        let player_a = test_env.player_a;
+        set_energy(&mut test_env, player_a, 10);
+
+        for expected_blueprint in [EntityBlueprint::Tower, EntityBlueprint::Watchtower] {
+            assert!(world_upgrade_building(
+                &mut test_env.state.dynamic_game_state,
+                tower_id,
+                test_env.player_a,
+            ));
+            let entity = &test_env.get_entity(tower_id).entity;
+            assert_eq!(entity.blueprint, Some(expected_blueprint));
+            assert_eq!(entity.health.health, entity.health.max_health);
+        }
+        assert!(!world_upgrade_building(
+            &mut test_env.state.dynamic_game_state,
+            tower_id,
+            test_env.player_a,
+        ));
+        assert_eq!(energy(&test_env, test_env.player_a), 5);
+        assert_eq!(test_env.get_entity(tower_id).entity.energy_invested, 5);
+    }
+
+    #[test]
+    fn test_upgrade_keeps_card_buffs() {
+        let mut test_env = TestEnvironment::default();
+        let player_a = test_env.player_a;
+        let tower_id = test_env.place_building(player_a, EntityBlueprint::SmallTower.create());
+        test_env.play_card(player_a, Card::ReinforcedDoors);
+        buff_add_to_entity(
+            Buff::AttackDamage(ArithmeticBuff::new_multiplicative(2.0)),
+            &mut find_entity_mut(
+                &mut test_env.state.dynamic_game_state.entities,
+                Some(tower_id),
+            )
+            .unwrap()
+            .entity,
+        );
+        set_energy(&mut test_env, player_a, 10);
+
+        assert!(world_upgrade_building(
+            &mut test_env.state.dynamic_game_state,
+            tower_id,
+            player_a,
+        ));
+        let entity = &test_env.get_entity(tower_id).entity;
+        assert_eq!(entity.blueprint, Some(EntityBlueprint::Tower));
+        assert_eq!(entity.health.extra_health_buffs.len(), 1);
+        assert_eq!(entity.health.extra_health_buffs[0].health, 200.0);
+        assert!(entity
+            .attacks
+            .iter()
+            .all(|attack| attack.damage_buffs.len() == 1));
+    }
+
+    #[test]
+    fn test_upgrade_is_validated() {
+        let mut test_env = TestEnvironment::default();
+        let tower_id =
+            test_env.place_building(test_env.player_a, EntityBlueprint::SmallTower.create());
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        set_energy(&mut test_env, player_a, 1);
+        set_energy(&mut test_env, player_b, 10);
+
+        assert!(!world_upgrade_building(
+            &mut test_env.state.dynamic_game_state,
+            tower_id,
+            test_env.player_a,
+        ));
+        assert!(!world_upgrade_building(
+            &mut test_env.state.dynamic_game_state,
+            tower_id,
+            test_env.player_b,
+        ));
+        assert_eq!(
+            test_env.get_entity(tower_id).entity.blueprint,
+            Some(EntityBlueprint::SmallTower)
+        );
+        assert_eq!(energy(&test_env, test_env.player_a), 1);
+        assert_eq!(energy(&test_env, test_env.player_b), 10);
+    }
+
+    #[test]
+    fn test_sell_refunds_and_frees_location() {
+        let mut test_env = TestEnvironment::default();
+        let tower_id = test_env.place_building(test_env.player_a, EntityBlueprint::Tower.create());
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(tower_id),
+        )
+        .unwrap()
+        .entity
+        .energy_invested = 4;
+
+        assert!(!world_sell_building(
+            &mut test_env.state.dynamic_game_state,
+            &test_env.state.static_game_state.ruleset,
+            tower_id,
+            test_env.player_b,
+        ));
+        assert!(world_sell_building(
+            &mut test_env.state.dynamic_game_state,
+            &test_env.state.static_game_state.ruleset,
+            tower_id,
+            test_env.player_a,
+        ));
+        assert_eq!(test_env.get_entity(tower_id).state, EntityState::Dead);
+        assert_eq!(energy(&test_env, test_env.player_a), 2);
+
+        assert!(test_env.simulate_frame().is_ok());
+        assert!(test_env
+            .state
+            .semi_static_game_state
+            .building_locations()
+            .values()
+            .all(|building_location| building_location.is_available()));
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        component_spawner::Spawner,
+        entity::{EntityState, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        ids::{EntityId, PlayerId},
+        world::find_entity_mut,
+    };
+
+    fn place_spawner(test_env: &mut TestEnvironment, spawner: Spawner) -> EntityId {
+        let mut building = EntityBlueprint::SpawnPoint.create();
+        building.spawner = Some(spawner);
+        test_env.place_building_at(test_env.player_a, building, (150.0, 150.0))
+    }
+
+    fn units_alive(test_env: &TestEnvironment, player_id: PlayerId) -> Vec<EntityId> {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|e| e.owner == player_id && e.entity.tag == EntityTag::Unit)
+            .map(|e| e.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_spawner_spawns_on_interval() {
+        let mut test_env = TestEnvironment::default();
+        place_spawner(
+            &mut test_env,
+            Spawner::new(EntityBlueprint::HomesickWarrior, 2.0),
+        );
+        assert!(test_env.simulate_for(1.9).is_ok());
+        assert!(units_alive(&test_env, test_env.player_a).is_empty());
+        assert!(test_env.simulate_for(0.2).is_ok());
+        assert_eq!(units_alive(&test_env, test_env.player_a).len(), 1);
+        assert!(test_env.simulate_for(2.0).is_ok());
+        assert_eq!(units_alive(&test_env, test_env.player_a).len(), 2);
+    }
+
+    #[test]
+    fn test_spawner_respects_max_alive() {
+        let mut test_env = TestEnvironment::default();
+        place_spawner(
+            &mut test_env,
+            Spawner::new(EntityBlueprint::HomesickWarrior, 1.0).with_max_alive(2),
+        );
+        assert!(test_env.simulate_for(5.0).is_ok());
+        let alive = units_alive(&test_env, test_env.player_a);
+        assert_eq!(alive.len(), 2);
+
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(alive[0]),
+        )
+        .unwrap()
+        .state = EntityState::Dead;
+        assert!(test_env.simulate_for(0.5).is_ok());
+        assert_eq!(units_alive(&test_env, test_env.player_a).len(), 1);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(units_alive(&test_env, test_env.player_a).len(), 2);
+    }
+
+    #[test]
+    fn test_spawner_pauses_while_damaged() {
+        let mut test_env = TestEnvironment::default();
+        let spawner_id = place_spawner(
+            &mut test_env,
+            Spawner::new(EntityBlueprint::HomesickWarrior, 1.0).with_pause_while_damaged(),
+        );
+        let health = &mut find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(spawner_id),
+        )
+        .unwrap()
+        .entity
+        .health;
+        health.health = health.max_health / 2.0;
+        assert!(test_env.simulate_for(3.0).is_ok());
+        assert!(units_alive(&test_env, test_env.player_a).is_empty());
+
+        let health = &mut find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(spawner_id),
+        )
+        .unwrap()
+        .entity
+        .health;
+        health.health = health.max_health;
+        assert!(test_env.simulate_for(1.1).is_ok());
+        assert_eq!(units_alive(&test_env, test_env.player_a).len(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        card::Card,
+        entity_blueprint::EntityBlueprint,
+        play_target::{EntityTarget, PlayTarget, WorldPosTarget},
+    };
+
+    #[test]
+    fn test_direct_damage_cannot_target_spell_immune() {
+        let mut test_env = TestEnvironment::default();
+        let immune =
+            test_env.play_entity(test_env.player_b, EntityBlueprint::OldSwordMaster.create());
+        let regular = test_env.play_entity(test_env.player_b, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_frames(2).is_ok());
+        let immune_health = test_env.get_entity(immune).entity.health.health;
+        let regular_health = test_env.get_entity(regular).entity.health.health;
+
+        assert!(!test_env.try_play_card_at(
+            test_env.player_a,
+            Card::DirectDamage,
+            Some(PlayTarget::Entity(EntityTarget { id: immune })),
+        ));
+        test_env.play_card_at(
+            test_env.player_a,
+            Card::DirectDamage,
+            Some(PlayTarget::Entity(EntityTarget { id: regular })),
+        );
+        assert_eq!(
+            test_env.get_entity(immune).entity.health.health,
+            immune_health
+        );
+        assert!(test_env.get_entity(regular).entity.health.health < regular_health);
+    }
+
+    #[test]
+    fn test_area_spells_skip_spell_immune() {
+        let mut test_env = TestEnvironment::default();
+        let immune =
+            test_env.play_entity(test_env.player_b, EntityBlueprint::OldSwordMaster.create());
+        let regular = test_env.play_entity(test_env.player_b, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_frames(2).is_ok());
+        let immune_health = test_env.get_entity(immune).entity.health.health;
+        let pos = test_env.get_entity_position(immune);
+        for card in [Card::LightningStrike, Card::FrostNova] {
+            test_env.play_card_at(
+                test_env.player_a,
+                card,
+                Some(PlayTarget::WorldPos(WorldPosTarget { x: pos.x, y: pos.y })),
+            );
+        }
+        test_env.play_card(test_env.player_a, Card::BlackDeath);
+
+        let immune_entity = &test_env.get_entity(immune).entity;
+        assert_eq!(immune_entity.health.health, immune_health);
+        assert!(!immune_entity.status_effects.is_stunned());
+        assert!(immune_entity.seconds_left_to_live.is_none());
+        let regular_entity = &test_env.get_entity(regular).entity;
+        assert!(regular_entity.status_effects.is_stunned());
+        assert!(regular_entity.seconds_left_to_live.is_some());
+    }
+}
@@ -1,12 +1,11 @@
 #[cfg(test)]
-
 pub mod test {
     use crate::{condition::Condition, test_environment::test::TestEnvironment};
     use common::{
         component_attack::Attack,
         component_health::Health,
         component_movement::{Movement, MovementSpeed},
-        component_spy::Spy,
+        component_stealth::Stealth,
         entity::Entity,
         entity_blueprint::EntityBlueprint,
         ids::EntityId,
@@ -18,7 +17,7 @@ pub mod test {
         let spy = Entity {
             health: Health::new(1.0),
             movement: Some(Movement::new(MovementSpeed::Fast)),
-            spy: Some(Spy::new(hide_capacity)),
+            stealth: Some(Stealth::hidden_for_encounters(hide_capacity)),
             attacks: vec![Attack {
                 damage: 10000.0,
                 ..Attack::default()
@@ -0,0 +1,138 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        card::Card,
+        component_status_effects::{StatusEffect, StatusEffectKind},
+        entity_blueprint::EntityBlueprint,
+        ids::EntityId,
+        play_target::{PlayTarget, WorldPosTarget},
+        world::find_entity_mut,
+    };
+
+    fn apply(test_env: &mut TestEnvironment, entity_id: EntityId, status_effect: StatusEffect) {
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(entity_id),
+        )
+        .unwrap()
+        .entity
+        .status_effects
+        .apply(status_effect);
+    }
+
+    #[test]
+    fn test_stun_and_root_prevent_movement() {
+        let mut test_env = TestEnvironment::default();
+        let stunned = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        let rooted = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        let free = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_frames(2).is_ok());
+        let [stunned_pos, rooted_pos, free_pos] =
+            [stunned, rooted, free].map(|id| test_env.get_entity_position(id));
+        apply(&mut test_env, stunned, StatusEffect::stun(2.0));
+        apply(&mut test_env, rooted, StatusEffect::root(2.0));
+
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(test_env.get_entity_position(stunned), stunned_pos);
+        assert_eq!(test_env.get_entity_position(rooted), rooted_pos);
+        assert!(test_env.get_entity_position(free).distance(free_pos) > 10.0);
+
+        assert!(test_env.simulate_for(2.0).is_ok());
+        assert!(test_env.get_entity_position(stunned).distance(stunned_pos) > 10.0);
+    }
+
+    #[test]
+    fn test_slow_reduces_speed() {
+        let mut test_env = TestEnvironment::default();
+        let slowed = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        let free = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_frames(2).is_ok());
+        let start_pos = test_env.get_entity_position(free);
+        apply(&mut test_env, slowed, StatusEffect::slow(0.5, 10.0));
+
+        assert!(test_env.simulate_for(2.0).is_ok());
+        let slowed_distance = test_env.get_entity_position(slowed).distance(start_pos);
+        let free_distance = test_env.get_entity_position(free).distance(start_pos);
+        assert!((slowed_distance / free_distance - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_poison_stacks_and_deals_damage() {
+        let mut test_env = TestEnvironment::default();
+        let wall = test_env.place_building(test_env.player_a, EntityBlueprint::Wall.create());
+        let max_health = test_env.get_entity(wall).entity.health.max_health;
+        for _ in 0..7 {
+            apply(&mut test_env, wall, StatusEffect::poison(5.0, 2.0));
+        }
+        let status_effects = &test_env.get_entity(wall).entity.status_effects;
+        assert_eq!(status_effects.effects.len(), 5);
+
+        assert!(test_env.simulate_for(3.0).is_ok());
+        let wall_entity = &test_env.get_entity(wall).entity;
+        assert!(!wall_entity.status_effects.has(StatusEffectKind::Poison));
+        assert!((max_health - wall_entity.health.health - 5.0 * 5.0 * 2.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_burn_refreshes_instead_of_stacking() {
+        let mut test_env = TestEnvironment::default();
+        let wall = test_env.place_building(test_env.player_a, EntityBlueprint::Wall.create());
+        apply(&mut test_env, wall, StatusEffect::burn(10.0, 1.0));
+        apply(&mut test_env, wall, StatusEffect::burn(10.0, 3.0));
+        let status_effects = &test_env.get_entity(wall).entity.status_effects;
+        assert_eq!(status_effects.effects.len(), 1);
+        assert_eq!(status_effects.effects[0].seconds_left, 3.0);
+    }
+
+    #[test]
+    fn test_status_effect_immunity() {
+        let mut test_env = TestEnvironment::default();
+        let mut entity = EntityBlueprint::DemonWolf.create();
+        entity
+            .status_effects
+            .immunities
+            .set(&StatusEffectKind::Stun);
+        let immune = test_env.play_entity(test_env.player_a, entity);
+        apply(&mut test_env, immune, StatusEffect::stun(2.0));
+        apply(&mut test_env, immune, StatusEffect::slow(0.5, 2.0));
+        let status_effects = &test_env.get_entity(immune).entity.status_effects;
+        assert!(!status_effects.is_stunned());
+        assert!(status_effects.has(StatusEffectKind::Slow));
+    }
+
+    #[test]
+    fn test_venom_archer_poisons_target() {
+        let mut test_env = TestEnvironment::default();
+        test_env.play_card(test_env.player_a, Card::VenomArcher);
+        let target =
+            test_env.play_entity(test_env.player_b, EntityBlueprint::HomesickWarrior.create());
+        let simulation_result = test_env
+            .simulate_until(Condition::EntitySatisfies(target, |entity| {
+                entity.status_effects.has(StatusEffectKind::Poison)
+            }));
+        assert!(simulation_result.is_ok());
+    }
+
+    #[test]
+    fn test_frost_nova_stuns_enemy_units() {
+        let mut test_env = TestEnvironment::default();
+        let enemy = test_env.play_entity(test_env.player_b, EntityBlueprint::DemonWolf.create());
+        let ally = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        let enemy_pos = test_env.get_entity_position(enemy);
+        let ally_pos = test_env.get_entity_position(ally);
+        for pos in [enemy_pos, ally_pos] {
+            test_env.play_card_at(
+                test_env.player_a,
+                Card::FrostNova,
+                Some(PlayTarget::WorldPos(WorldPosTarget { x: pos.x, y: pos.y })),
+            );
+        }
+        assert!(test_env
+            .get_entity(enemy)
+            .entity
+            .status_effects
+            .is_stunned());
+        assert!(!test_env.get_entity(ally).entity.status_effects.is_stunned());
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::{Attack, TargetPool},
+        component_buff_source::{AuraEffect, AuraSource, BuffRange},
+        component_health::Health,
+        component_movement::{Movement, MovementSpeed},
+        component_stealth::Stealth,
+        entity::Entity,
+        entity_blueprint::EntityBlueprint,
+        entity_filter::EntityFilter,
+        ids::{EntityId, PlayerId},
+    };
+    use macroquad::math::Vec2;
+
+    fn place_tower(test_env: &mut TestEnvironment, player_id: PlayerId, detects: bool) {
+        let mut tower = Entity::default_tower();
+        tower.health = Health::new(1000.0);
+        tower.attacks = vec![Attack {
+            damage: 1000.0,
+            ..Attack::default_ranged()
+        }];
+        if detects {
+            tower.aura_sources = vec![AuraSource {
+                effect: AuraEffect::Reveal,
+                target_filter: EntityFilter {
+                    range_filter: Some(BuffRange::Default),
+                    pool_filter: Some(TargetPool::Enemies),
+                    tag_filter: None,
+                },
+            }];
+        }
+        test_env.place_building_at(player_id, tower, (600.0, 100.0));
+    }
+
+    fn play_stealthed_unit(test_env: &mut TestEnvironment, player_id: PlayerId) -> EntityId {
+        test_env.play_entity(
+            player_id,
+            Entity {
+                health: Health::new(100.0),
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                stealth: Some(Stealth::stealthed()),
+                ..Entity::default_unit()
+            },
+        )
+    }
+
+    #[test]
+    fn test_stealthed_unit_sneaks_past_tower() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        place_tower(&mut test_env, player_b, false);
+        let unit_id = play_stealthed_unit(&mut test_env, player_a);
+        assert!(test_env
+            .simulate(|env| env.get_entity_position(unit_id).x > 900.0)
+            .is_ok());
+        let health = &test_env.get_entity(unit_id).entity.health;
+        assert_eq!(health.health, health.max_health);
+    }
+
+    #[test]
+    fn test_detector_reveals_stealthed_unit() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        place_tower(&mut test_env, player_b, true);
+        let unit_id = play_stealthed_unit(&mut test_env, player_a);
+        assert!(test_env
+            .simulate_until(Condition::EntityIsDead(unit_id))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_camouflage_breaks_on_first_attack() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        let camouflaged = Entity {
+            health: Health::new(500.0),
+            movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+            attacks: vec![Attack::default()],
+            stealth: Some(Stealth::camouflaged()),
+            ..Entity::default_unit()
+        }
+        .instantiate(player_a, Vec2::new(600.0, 200.0));
+        let camouflaged_id = camouflaged.id;
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .spawn(camouflaged);
+        assert!(test_env.get_entity(camouflaged_id).entity.is_concealed());
+        test_env.play_entity(
+            player_b,
+            Entity {
+                health: Health::new(500.0),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        );
+        assert!(test_env
+            .simulate(|env| !env.get_entity(camouflaged_id).entity.is_concealed())
+            .is_ok());
+        assert!(test_env
+            .simulate(|env| {
+                let health = &env.get_entity(camouflaged_id).entity.health;
+                health.health < health.max_health
+            })
+            .is_ok());
+    }
+
+    #[test]
+    fn test_hidden_units_are_not_replicated_to_enemies() {
+        let mut test_env = TestEnvironment::default();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        let unit_id = play_stealthed_unit(&mut test_env, player_a);
+        assert!(test_env.simulate_frame().is_ok());
+        let is_visible_to = |player_id| {
+            test_env
+                .state
+                .dynamic_game_state
+                .visible_to(player_id)
+                .entities
+                .iter()
+                .any(|entity_instance| entity_instance.id == unit_id)
+        };
+        assert!(is_visible_to(player_a));
+        assert!(!is_visible_to(player_b));
+    }
+}
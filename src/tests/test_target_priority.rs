@@ -0,0 +1,197 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        component_attack::{Attack, AttackInterval, AttackRange, AttackVariant, TargetPriority},
+        component_health::Health,
+        component_movement::{Movement, MovementSpeed},
+        entity::{Entity, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        enum_flags::{flags, EnumFlags},
+        ids::EntityId,
+        world::find_entity_mut,
+    };
+    use macroquad::math::Vec2;
+
+    /// A tower at (600, 100) that reaches the whole lane
+    fn place_tower(
+        test_env: &mut TestEnvironment,
+        target_priority: TargetPriority,
+        attack_interval: f32,
+    ) {
+        let tower = Entity {
+            attacks: vec![Attack {
+                damage: 1.0,
+                variant: AttackVariant::MeleeAttack,
+                range: AttackRange::Custom(1000.0),
+                attack_interval: AttackInterval::Custom(attack_interval),
+                can_target: flags![EntityTag::Tower, EntityTag::Unit, EntityTag::FlyingUnit],
+                target_priority,
+                ..Attack::default()
+            }],
+            health: Health::new(1000.0),
+            ..Entity::default_tower()
+        };
+        test_env.place_building_at(test_env.player_a, tower, (600.0, 100.0));
+    }
+
+    /// An enemy unit standing still at x on the lane
+    fn play_unit_at(test_env: &mut TestEnvironment, entity: Entity, x: f32) -> EntityId {
+        let entity_id = test_env.play_entity(
+            test_env.player_b,
+            Entity {
+                movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+                ..entity
+            },
+        );
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(entity_id),
+        )
+        .unwrap()
+        .pos = Vec2::new(x, 200.0);
+        entity_id
+    }
+
+    fn play_units(test_env: &mut TestEnvironment, xs: &[f32]) -> Vec<EntityId> {
+        xs.iter()
+            .map(|x| play_unit_at(test_env, EntityBlueprint::StreetCriminal.create(), *x))
+            .collect()
+    }
+
+    fn damage_taken(test_env: &TestEnvironment, entity_id: EntityId) -> f32 {
+        test_env
+            .get_entity(entity_id)
+            .entity
+            .health
+            .get_missing_health()
+    }
+
+    /// Simulates the first attack and returns the index of the entity that was hit
+    fn first_hit(test_env: &mut TestEnvironment, entity_ids: &[EntityId]) -> usize {
+        let ids = entity_ids.to_vec();
+        assert!(test_env
+            .simulate(|env| ids.iter().any(|id| damage_taken(env, *id) > 0.0))
+            .is_ok());
+        let hit: Vec<usize> = (0..entity_ids.len())
+            .filter(|i| damage_taken(test_env, entity_ids[*i]) > 0.0)
+            .collect();
+        assert_eq!(hit.len(), 1);
+        hit[0]
+    }
+
+    #[test]
+    fn test_nearest() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, &[300.0, 550.0, 900.0]);
+        place_tower(&mut test_env, TargetPriority::Nearest, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 1);
+    }
+
+    #[test]
+    fn test_lowest_health() {
+        let mut test_env = TestEnvironment::default();
+        let mut unit_ids = play_units(&mut test_env, &[550.0, 900.0]);
+        unit_ids.push(play_unit_at(
+            &mut test_env,
+            Entity {
+                health: Health::new(50.0),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+            1000.0,
+        ));
+        place_tower(&mut test_env, TargetPriority::LowestHealth, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 2);
+    }
+
+    #[test]
+    fn test_highest_health() {
+        let mut test_env = TestEnvironment::default();
+        let mut unit_ids = play_units(&mut test_env, &[550.0, 900.0]);
+        unit_ids.push(play_unit_at(
+            &mut test_env,
+            Entity {
+                health: Health::new(1000.0),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+            1000.0,
+        ));
+        place_tower(&mut test_env, TargetPriority::HighestHealth, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 2);
+    }
+
+    #[test]
+    fn test_highest_damage() {
+        let mut test_env = TestEnvironment::default();
+        let mut unit_ids = play_units(&mut test_env, &[550.0, 900.0]);
+        unit_ids.push(play_unit_at(
+            &mut test_env,
+            EntityBlueprint::OldSwordMaster.create(),
+            1000.0,
+        ));
+        place_tower(&mut test_env, TargetPriority::HighestDamage, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 2);
+    }
+
+    #[test]
+    fn test_furthest_along_path() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, &[900.0, 550.0, 300.0]);
+        place_tower(&mut test_env, TargetPriority::FurthestAlongPath, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 2);
+    }
+
+    #[test]
+    fn test_furthest_along_path_counts_flyers() {
+        let mut test_env = TestEnvironment::default();
+        let mut unit_ids = play_units(&mut test_env, &[300.0]);
+        // Not on a path, but closer to player a's base than the walking unit
+        let flyer_id = test_env.play_entity(
+            test_env.player_b,
+            Entity {
+                movement: Some(Movement::new_flying(MovementSpeed::Custom(0.0))),
+                ..Entity::default_flying_unit()
+            },
+        );
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(flyer_id),
+        )
+        .unwrap()
+        .pos = Vec2::new(150.0, 100.0);
+        unit_ids.push(flyer_id);
+        place_tower(&mut test_env, TargetPriority::FurthestAlongPath, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 1);
+    }
+
+    #[test]
+    fn test_buildings_first() {
+        let mut test_env = TestEnvironment::default();
+        let mut unit_ids = play_units(&mut test_env, &[550.0]);
+        unit_ids.push(test_env.place_building_at(
+            test_env.player_b,
+            EntityBlueprint::Wall.create(),
+            (900.0, 100.0),
+        ));
+        place_tower(&mut test_env, TargetPriority::BuildingsFirst, 100.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 1);
+    }
+
+    #[test]
+    fn test_keep_current_target() {
+        let mut test_env = TestEnvironment::default();
+        let unit_ids = play_units(&mut test_env, &[550.0, 900.0]);
+        place_tower(&mut test_env, TargetPriority::KeepCurrentTarget, 1.0);
+        assert_eq!(first_hit(&mut test_env, &unit_ids), 0);
+
+        // A closer enemy does not steal the tower's attention
+        let closer_id = play_unit_at(
+            &mut test_env,
+            EntityBlueprint::StreetCriminal.create(),
+            600.0,
+        );
+        assert!(test_env.simulate_for(2.5).is_ok());
+        assert_eq!(damage_taken(&test_env, closer_id), 0.0);
+        assert!(damage_taken(&test_env, unit_ids[0]) >= 3.0);
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::TargetPool,
+        component_buff_source::{AuraEffect, AuraSource, BuffRange},
+        component_health::Health,
+        component_movement::{Movement, MovementSpeed},
+        entity::{Entity, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        entity_filter::EntityFilter,
+        ids::{EntityId, PlayerId},
+        level_config::LevelPreset,
+    };
+    use macroquad::math::Vec2;
+
+    fn spawn_unit_at(
+        test_env: &mut TestEnvironment,
+        player_id: PlayerId,
+        entity: Entity,
+        pos: (f32, f32),
+    ) -> EntityId {
+        let entity_instance = entity.instantiate(player_id, Vec2::from(pos));
+        let entity_id = entity_instance.id;
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .spawn(entity_instance);
+        entity_id
+    }
+
+    fn standing_fighter() -> Entity {
+        Entity {
+            health: Health::new(500.0),
+            movement: Some(Movement::new(MovementSpeed::Custom(0.0))),
+            ..EntityBlueprint::StreetCriminal.create()
+        }
+    }
+
+    fn destroy_base(test_env: &mut TestEnvironment, player_id: PlayerId) {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .retain(|entity_instance| {
+                entity_instance.entity.tag != EntityTag::Base || entity_instance.owner != player_id
+            });
+    }
+
+    #[test]
+    fn test_teammates_do_not_attack_each_other() {
+        let mut test_env = TestEnvironment::new(LevelPreset::CrossroadsTeams.create());
+        let player_ids = test_env.player_ids.clone();
+        let unit_a = spawn_unit_at(
+            &mut test_env,
+            player_ids[0],
+            standing_fighter(),
+            (600.0, 576.0),
+        );
+        let unit_b = spawn_unit_at(
+            &mut test_env,
+            player_ids[1],
+            standing_fighter(),
+            (610.0, 576.0),
+        );
+        assert!(test_env.simulate_for(3.0).is_ok());
+        for unit_id in [unit_a, unit_b] {
+            let health = &test_env.get_entity(unit_id).entity.health;
+            assert_eq!(health.health, health.max_health);
+        }
+
+        let enemy_id = spawn_unit_at(
+            &mut test_env,
+            player_ids[2],
+            standing_fighter(),
+            (620.0, 576.0),
+        );
+        assert!(test_env
+            .simulate(|env| {
+                let health = &env.get_entity(enemy_id).entity.health;
+                health.health < health.max_health
+            })
+            .is_ok());
+    }
+
+    #[test]
+    fn test_aura_buffs_teammates() {
+        let mut test_env = TestEnvironment::new(LevelPreset::CrossroadsTeams.create());
+        let player_ids = test_env.player_ids.clone();
+        let mut shrine = Entity::default_tower();
+        shrine.health = Health::new(1000.0);
+        shrine.attacks = Vec::new();
+        shrine.aura_sources = vec![AuraSource {
+            effect: AuraEffect::HealthRegeneration(50.0),
+            target_filter: EntityFilter {
+                range_filter: Some(BuffRange::Default),
+                pool_filter: Some(TargetPool::Allies),
+                tag_filter: None,
+            },
+        }];
+        test_env.place_building_at(player_ids[0], shrine, (600.0, 500.0));
+        let mut wounded = Entity {
+            attacks: Vec::new(),
+            ..standing_fighter()
+        };
+        wounded.health.health = 100.0;
+        let teammate_id = spawn_unit_at(
+            &mut test_env,
+            player_ids[1],
+            wounded.clone(),
+            (600.0, 576.0),
+        );
+        let enemy_id = spawn_unit_at(&mut test_env, player_ids[2], wounded, (620.0, 576.0));
+        assert!(test_env.simulate_for(2.0).is_ok());
+        assert!(test_env.get_entity(teammate_id).entity.health.health > 100.0);
+        assert_eq!(test_env.get_entity(enemy_id).entity.health.health, 100.0);
+    }
+
+    #[test]
+    fn test_team_wins_when_all_enemy_bases_fall() {
+        let mut test_env = TestEnvironment::new(LevelPreset::CrossroadsTeams.create());
+        let player_ids = test_env.player_ids.clone();
+        destroy_base(&mut test_env, player_ids[1]);
+        destroy_base(&mut test_env, player_ids[2]);
+        assert!(test_env.simulate_frame().is_ok());
+        assert_eq!(test_env.state.dynamic_game_state.get_winning_team(), None);
+
+        destroy_base(&mut test_env, player_ids[3]);
+        assert!(test_env.simulate_frame().is_ok());
+        assert!(Condition::PlayerWon(player_ids[0]).is_met(&test_env));
+        assert!(Condition::PlayerWon(player_ids[1]).is_met(&test_env));
+        assert!(!Condition::PlayerWon(player_ids[2]).is_met(&test_env));
+    }
+
+    #[test]
+    fn test_free_for_all_unit_turns_back_towards_enemy_on_its_side() {
+        let mut test_env = TestEnvironment::new(LevelPreset::CrossroadsFreeForAll.create());
+        let player_ids = test_env.player_ids.clone();
+        // Only the other western base is left, which the lanes lead away from
+        destroy_base(&mut test_env, player_ids[2]);
+        destroy_base(&mut test_env, player_ids[3]);
+        test_env.play_entity(
+            player_ids[1],
+            Entity {
+                health: Health::new(500.0),
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        );
+        assert!(test_env
+            .simulate_until(Condition::PlayerWon(player_ids[1]))
+            .is_ok());
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{
+        card::Card,
+        component_attack::TargetPool,
+        component_buff_source::{AuraEffect, AuraSource, BuffRange},
+        component_health::Health,
+        component_movement::{Movement, MovementSpeed},
+        component_status_effects::StatusEffectKind,
+        entity::{Entity, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        entity_filter::EntityFilter,
+        ids::{EntityId, PlayerId},
+        play_target::{get_path_node_targets, PlayTarget, WorldPosTarget},
+        world::get_path_pos,
+    };
+    use macroquad::math::Vec2;
+
+    const TRAP_POS: (f32, f32) = (600.0, 200.0);
+
+    fn test_env_with_trap_node() -> TestEnvironment {
+        let mut level_config = TestEnvironment::default_level_config();
+        level_config.paths = vec![vec![(100.0, 200.0), TRAP_POS, (1100.0, 200.0)]];
+        TestEnvironment::new(level_config)
+    }
+
+    fn play_trap(test_env: &mut TestEnvironment, player_id: PlayerId, card: Card) {
+        let static_game_state = &test_env.state.static_game_state;
+        let target = get_path_node_targets(static_game_state)
+            .into_iter()
+            .find(|target| {
+                get_path_pos(static_game_state, target.path_id, target.path_idx)
+                    == Vec2::from(TRAP_POS)
+            })
+            .unwrap();
+        test_env.play_card_at(player_id, card, Some(PlayTarget::PathNode(target)));
+    }
+
+    fn play_walking_unit(test_env: &mut TestEnvironment, player_id: PlayerId) -> EntityId {
+        test_env.play_entity(
+            player_id,
+            Entity {
+                health: Health::new(500.0),
+                movement: Some(Movement::new(MovementSpeed::Fast)),
+                attacks: Vec::new(),
+                ..EntityBlueprint::StreetCriminal.create()
+            },
+        )
+    }
+
+    fn trap_count(test_env: &TestEnvironment) -> usize {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|entity_instance| entity_instance.entity.tag == EntityTag::Trap)
+            .count()
+    }
+
+    #[test]
+    fn test_spike_trap_damages_enemy_and_is_used_up() {
+        let mut test_env = test_env_with_trap_node();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_trap(&mut test_env, player_a, Card::SpikeTrap);
+        let unit_id = play_walking_unit(&mut test_env, player_b);
+        assert!(test_env
+            .simulate(|env| env.get_entity(unit_id).entity.health.health < 500.0)
+            .is_ok());
+        assert_eq!(test_env.get_entity(unit_id).entity.health.health, 350.0);
+        assert!(test_env.simulate_frame().is_ok());
+        assert_eq!(trap_count(&test_env), 0);
+    }
+
+    #[test]
+    fn test_snare_and_stun_traps() {
+        for (card, kind) in [
+            (Card::SnareTrap, StatusEffectKind::Root),
+            (Card::StunTrap, StatusEffectKind::Stun),
+        ] {
+            let mut test_env = test_env_with_trap_node();
+            let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+            play_trap(&mut test_env, player_a, card);
+            let unit_id = play_walking_unit(&mut test_env, player_b);
+            assert!(test_env
+                .simulate(|env| env
+                    .get_entity(unit_id)
+                    .entity
+                    .status_effects
+                    .has(kind.clone()))
+                .is_ok());
+            assert!((test_env.get_entity_position(unit_id).x - TRAP_POS.0).abs() < 100.0);
+        }
+    }
+
+    #[test]
+    fn test_allies_and_flying_units_do_not_spring_traps() {
+        let mut test_env = test_env_with_trap_node();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_trap(&mut test_env, player_a, Card::SpikeTrap);
+        let ally_id = play_walking_unit(&mut test_env, player_a);
+        test_env.play_entity(
+            player_b,
+            Entity {
+                health: Health::new(100.0),
+                ..Entity::default_flying_unit()
+            },
+        );
+        assert!(test_env
+            .simulate(|env| env.get_entity_position(ally_id).x > 800.0)
+            .is_ok());
+        assert!(test_env.simulate_for(4.0).is_ok());
+        assert_eq!(trap_count(&test_env), 1);
+    }
+
+    #[test]
+    fn test_traps_are_hidden_from_other_players() {
+        let mut test_env = test_env_with_trap_node();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_trap(&mut test_env, player_a, Card::SpikeTrap);
+        assert!(test_env.simulate_frame().is_ok());
+        let visible_trap_count = |player_id| {
+            test_env
+                .state
+                .dynamic_game_state
+                .visible_to(player_id)
+                .entities
+                .iter()
+                .filter(|entity_instance| entity_instance.entity.tag == EntityTag::Trap)
+                .count()
+        };
+        assert_eq!(visible_trap_count(player_a), 1);
+        assert_eq!(visible_trap_count(player_b), 0);
+    }
+
+    #[test]
+    fn test_cannot_stack_own_traps() {
+        let mut test_env = test_env_with_trap_node();
+        let player_a = test_env.player_a;
+        play_trap(&mut test_env, player_a, Card::SpikeTrap);
+        let target = get_path_node_targets(&test_env.state.static_game_state)
+            .into_iter()
+            .find(|target| {
+                get_path_pos(
+                    &test_env.state.static_game_state,
+                    target.path_id,
+                    target.path_idx,
+                ) == Vec2::from(TRAP_POS)
+            })
+            .unwrap();
+        assert!(!test_env.try_play_card_at(
+            player_a,
+            Card::SnareTrap,
+            Some(PlayTarget::PathNode(target))
+        ));
+    }
+
+    #[test]
+    fn test_area_effects_do_not_reach_traps() {
+        let mut test_env = test_env_with_trap_node();
+        let (player_a, player_b) = (test_env.player_a, test_env.player_b);
+        play_trap(&mut test_env, player_a, Card::SpikeTrap);
+        let mut burning_tower = Entity::default_tower();
+        burning_tower.health = Health::new(1000.0);
+        burning_tower.attacks = Vec::new();
+        burning_tower.aura_sources = vec![AuraSource {
+            effect: AuraEffect::ContinuousDamage(100.0),
+            target_filter: EntityFilter {
+                range_filter: Some(BuffRange::Default),
+                pool_filter: Some(TargetPool::Enemies),
+                tag_filter: None,
+            },
+        }];
+        test_env.place_building_at(player_b, burning_tower, (600.0, 100.0));
+        test_env.play_card_at(
+            player_b,
+            Card::LightningStrike,
+            Some(PlayTarget::WorldPos(WorldPosTarget {
+                x: TRAP_POS.0,
+                y: TRAP_POS.1,
+            })),
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(trap_count(&test_env), 1);
+    }
+}
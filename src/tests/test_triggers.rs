@@ -0,0 +1,164 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{condition::Condition, test_environment::test::TestEnvironment};
+    use common::{
+        component_attack::{AttackRange, DamageType},
+        component_health::Health,
+        component_trigger::{Trigger, TriggerEffect, TriggerEvent},
+        entity::{Entity, EntityState, EntityTag},
+        entity_blueprint::EntityBlueprint,
+        enum_flags::{flags, EnumFlags},
+        ids::{EntityId, PlayerId},
+        world::find_entity_mut,
+    };
+
+    fn with_trigger(entity_blueprint: EntityBlueprint, trigger: Trigger) -> Entity {
+        let mut entity = entity_blueprint.create();
+        entity.triggers.push(trigger);
+        entity
+    }
+
+    fn spawn_small_criminals(count: usize) -> TriggerEffect {
+        TriggerEffect::SpawnEntities {
+            entity_blueprint: EntityBlueprint::SmallCriminal,
+            count,
+        }
+    }
+
+    fn small_criminals_alive(test_env: &TestEnvironment, player_id: PlayerId) -> usize {
+        test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|e| {
+                e.owner == player_id && e.entity.blueprint == Some(EntityBlueprint::SmallCriminal)
+            })
+            .count()
+    }
+
+    fn kill(test_env: &mut TestEnvironment, entity_id: EntityId) {
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(entity_id),
+        )
+        .unwrap()
+        .state = EntityState::Dead;
+    }
+
+    #[test]
+    fn test_on_spawn() {
+        let mut test_env = TestEnvironment::default();
+        test_env.play_entity(
+            test_env.player_a,
+            with_trigger(
+                EntityBlueprint::HomesickWarrior,
+                Trigger::new(TriggerEvent::OnSpawn, spawn_small_criminals(1)),
+            ),
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(small_criminals_alive(&test_env, test_env.player_a), 1);
+    }
+
+    #[test]
+    fn test_on_death_spawns_and_continues_path() {
+        let mut test_env = TestEnvironment::default();
+        let entity_id = test_env.play_entity(
+            test_env.player_a,
+            with_trigger(
+                EntityBlueprint::StreetCriminal,
+                Trigger::new(TriggerEvent::OnDeath, spawn_small_criminals(2)),
+            ),
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        let death_pos = test_env.get_entity_position(entity_id);
+        kill(&mut test_env, entity_id);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(small_criminals_alive(&test_env, test_env.player_a), 2);
+        assert!(test_env
+            .state
+            .dynamic_game_state
+            .entities
+            .iter()
+            .filter(|e| e.entity.blueprint == Some(EntityBlueprint::SmallCriminal))
+            .all(|e| e.pos.x > death_pos.x));
+    }
+
+    #[test]
+    fn test_on_death_damage() {
+        let mut test_env = TestEnvironment::default();
+        let entity_id = test_env.play_entity(
+            test_env.player_a,
+            with_trigger(
+                EntityBlueprint::HomesickWarrior,
+                Trigger::new(
+                    TriggerEvent::OnDeath,
+                    TriggerEffect::Damage {
+                        damage: 1000.0,
+                        damage_type: DamageType::Magic,
+                        range: AttackRange::Default,
+                        can_target: flags![EntityTag::Unit],
+                    },
+                ),
+            ),
+        );
+        test_env.play_entity(test_env.player_b, EntityBlueprint::StreetCriminal.create());
+        assert!(test_env
+            .simulate_until(Condition::EntityIsInState(
+                entity_id,
+                EntityState::Attacking
+            ))
+            .is_ok());
+        kill(&mut test_env, entity_id);
+        assert!(test_env.simulate_until(Condition::NoUnitsAlive).is_ok());
+    }
+
+    #[test]
+    fn test_on_damaged() {
+        let mut test_env = TestEnvironment::default();
+        let entity_id = test_env.play_entity(
+            test_env.player_a,
+            with_trigger(
+                EntityBlueprint::HomesickWarrior,
+                Trigger::new(TriggerEvent::OnDamaged, spawn_small_criminals(1)),
+            ),
+        );
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(small_criminals_alive(&test_env, test_env.player_a), 0);
+
+        find_entity_mut(
+            &mut test_env.state.dynamic_game_state.entities,
+            Some(entity_id),
+        )
+        .unwrap()
+        .entity
+        .health
+        .deal_damage(1.0);
+        assert!(test_env.simulate_for(1.0).is_ok());
+        assert_eq!(small_criminals_alive(&test_env, test_env.player_a), 1);
+    }
+
+    #[test]
+    fn test_on_kill_credits_projectile_source() {
+        let mut test_env = TestEnvironment::default();
+        test_env.play_entity(
+            test_env.player_a,
+            with_trigger(
+                EntityBlueprint::ElfWarrior,
+                Trigger::new(TriggerEvent::OnKill, spawn_small_criminals(1)),
+            ),
+        );
+        let target_id = test_env.play_entity(
+            test_env.player_b,
+            Entity {
+                health: Health::new(1.0),
+                ..EntityBlueprint::HomesickWarrior.create()
+            },
+        );
+        assert!(test_env
+            .simulate_until(Condition::EntityIsDead(target_id))
+            .is_ok());
+        assert!(test_env.simulate_frame().is_ok());
+        assert_eq!(small_criminals_alive(&test_env, test_env.player_a), 1);
+    }
+}
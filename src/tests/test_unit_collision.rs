@@ -0,0 +1,51 @@
+#[cfg(test)]
+pub mod test {
+    use crate::test_environment::test::TestEnvironment;
+    use common::{config::UNIT_RADIUS, entity_blueprint::EntityBlueprint};
+
+    #[test]
+    fn test_ground_units_separate() {
+        let mut test_env = TestEnvironment::default();
+        let a = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        let b = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_for(2.0).is_ok());
+        let distance = test_env
+            .get_entity_position(a)
+            .distance(test_env.get_entity_position(b));
+        assert!(distance > 0.75 * UNIT_RADIUS);
+    }
+
+    #[test]
+    fn test_flying_units_overlap() {
+        let mut test_env = TestEnvironment::default();
+        let a = test_env.play_entity(test_env.player_a, EntityBlueprint::WarEagle.create());
+        let b = test_env.play_entity(test_env.player_a, EntityBlueprint::WarEagle.create());
+        assert!(test_env.simulate_for(2.0).is_ok());
+        let distance = test_env
+            .get_entity_position(a)
+            .distance(test_env.get_entity_position(b));
+        assert!(distance < 1.0);
+    }
+
+    fn fast_unit_passes(slow_unit: EntityBlueprint) -> bool {
+        let mut test_env = TestEnvironment::default();
+        let slow = test_env.play_entity(test_env.player_a, slow_unit.create());
+        assert!(test_env.simulate_for(1.0).is_ok());
+        let start_pos = test_env.get_entity_position(slow);
+        let fast = test_env.play_entity(test_env.player_a, EntityBlueprint::DemonWolf.create());
+        assert!(test_env.simulate_for(6.0).is_ok());
+        let slow_progress = (test_env.get_entity_position(slow).x - start_pos.x).abs();
+        let fast_progress = (test_env.get_entity_position(fast).x - start_pos.x).abs();
+        fast_progress > slow_progress
+    }
+
+    #[test]
+    fn test_allies_walk_around_slow_units() {
+        assert!(fast_unit_passes(EntityBlueprint::OldSwordMaster));
+    }
+
+    #[test]
+    fn test_allies_can_not_pass_lane_blocker() {
+        assert!(!fast_unit_passes(EntityBlueprint::ShieldBearer));
+    }
+}